@@ -0,0 +1,2 @@
+// Cargo only sets `OUT_DIR`, where `macros!(persist)` saves the sets, for packages with a build script.
+fn main() {}
@@ -0,0 +1,86 @@
+#![cfg_attr(feature = "fn_traits", feature(fn_traits, unboxed_closures))]
+//! Library saving its overload sets with `macros!(persist)`, which its integration tests and doctests generate again
+//! with `macros!(import = "fixture_persist")`.
+//!
+//! ```
+//! overfn::macros!(import = "fixture_persist");
+//!
+//! # fn main() {
+//! assert_eq!(add!(1, 2), 3);
+//! # }
+//! ```
+//!
+//! Sets the library never persisted can't be imported:
+//!
+//! ```compile_fail
+//! overfn::macros!(import = "unknown_crate");
+//! # fn main() {}
+//! ```
+//!
+//! Nor can the overloads marked `internal`:
+//!
+//! ```compile_fail
+//! overfn::macros!(import = "fixture_persist");
+//!
+//! # fn main() {
+//! add!(1, 2, 3);
+//! # }
+//! ```
+use overfn::*;
+
+#[overload]
+pub fn add(item: usize) -> usize {
+    item
+}
+
+#[overload]
+pub fn add(left: usize, right: usize) -> usize {
+    left + right
+}
+
+#[overload(internal)]
+pub fn add(first: usize, second: usize, third: usize) -> usize {
+    first + second + third
+}
+
+pub struct Counter(pub usize);
+
+impl Counter {
+    #[overload(Counter)]
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    #[overload(Counter)]
+    pub fn new(start: usize) -> Self {
+        Self(start)
+    }
+
+    #[overload(Counter)]
+    pub fn bump(&mut self) -> usize {
+        self.0 += 1;
+        self.0
+    }
+
+    #[overload(Counter)]
+    pub fn bump(&mut self, by: usize) -> usize {
+        self.0 += by;
+        self.0
+    }
+}
+
+pub mod shapes {
+    use overfn::overload;
+
+    #[overload(module = shapes)]
+    pub fn area(side: u32) -> u32 {
+        side * side
+    }
+
+    #[overload(module = shapes)]
+    pub fn area(width: u32, height: u32) -> u32 {
+        width * height
+    }
+}
+
+macros!(persist);
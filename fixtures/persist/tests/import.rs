@@ -0,0 +1,22 @@
+overfn::macros!(import = "fixture_persist");
+
+#[test]
+fn test_free_functions() {
+    assert_eq!(add!(1), 1);
+    assert_eq!(add!(1, 2), 3);
+    assert_eq!(add!(..[1, 2]), 3);
+}
+
+#[test]
+fn test_methods() {
+    let mut counter = Counter_new!(2);
+    assert_eq!(Counter_bump!(counter), 3);
+    assert_eq!(Counter_bump!(counter, 4), 7);
+    assert_eq!(Counter_new!().0, 0);
+}
+
+#[test]
+fn test_module_paths() {
+    assert_eq!(area!(3), 9);
+    assert_eq!(area!(2, 5), 10);
+}
@@ -0,0 +1,26 @@
+#![cfg_attr(feature = "fn_traits", feature(fn_traits, unboxed_closures))]
+//! Library generating the pyo3 function of a set with `#[overload(pyo3)]`, registered in its Python module.
+use overfn::*;
+use pyo3::prelude::*;
+
+#[overload(pyo3)]
+pub fn add(left: usize) -> usize {
+    10 + left
+}
+
+#[overload]
+pub fn add(left: usize, right: usize) -> usize {
+    left + right
+}
+
+#[overload]
+pub fn add(left: usize, right: usize, other: usize) -> usize {
+    left + right + other
+}
+
+macros!();
+
+#[pymodule]
+fn fixture_pyo3(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(add_py, module)?)
+}
@@ -0,0 +1,27 @@
+use fixture_pyo3::add_py;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+#[test]
+fn test_rust_calls() {
+    assert_eq!(add_py(1, None, None).unwrap(), 11);
+    assert_eq!(add_py(1, Some(2), None).unwrap(), 3);
+    assert_eq!(add_py(1, Some(2), Some(3)).unwrap(), 6);
+    assert!(add_py(1, None, Some(3)).is_err());
+}
+
+#[test]
+fn test_python_calls() {
+    Python::with_gil(|py| {
+        let add = wrap_pyfunction_bound!(add_py, py).unwrap();
+        assert_eq!(add.getattr("__name__").unwrap().extract::<String>().unwrap(), "add");
+        assert_eq!(add.call1((1,)).unwrap().extract::<usize>().unwrap(), 11);
+        assert_eq!(add.call1((1, 2)).unwrap().extract::<usize>().unwrap(), 3);
+        assert_eq!(add.call1((1, 2, 3)).unwrap().extract::<usize>().unwrap(), 6);
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("other", 3).unwrap();
+        let error = add.call((1,), Some(&kwargs)).unwrap_err();
+        assert!(error.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+        assert!(add.call1((1, 2, 3, 4)).is_err());
+    });
+}
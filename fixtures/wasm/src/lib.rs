@@ -0,0 +1,20 @@
+#![cfg_attr(feature = "fn_traits", feature(fn_traits, unboxed_closures))]
+//! Library generating the wasm-bindgen function of a set with `#[overload(wasm)]`, exported to JavaScript as `add`.
+use overfn::*;
+
+#[overload(wasm)]
+pub fn add(left: u32) -> u32 {
+    10 + left
+}
+
+#[overload]
+pub fn add(left: u32, right: u32) -> u32 {
+    left + right
+}
+
+#[overload]
+pub fn add(left: u32, right: u32, other: u32) -> u32 {
+    left + right + other
+}
+
+macros!();
@@ -0,0 +1,16 @@
+use fixture_wasm::add_js;
+use wasm_bindgen::JsError;
+
+#[test]
+fn test_signature() {
+    let _: fn(u32, Option<u32>, Option<u32>) -> Result<u32, JsError> = add_js;
+}
+
+// Building the `JsError` of a skipped argument calls into JavaScript, so only the calls matching an overload run on
+// the host.
+#[test]
+fn test_rust_calls() {
+    assert_eq!(add_js(1, None, None).ok(), Some(11));
+    assert_eq!(add_js(1, Some(2), None).ok(), Some(3));
+    assert_eq!(add_js(1, Some(2), Some(3)).ok(), Some(6));
+}
@@ -0,0 +1,753 @@
+//! Arguments accepted by the macros of the crate.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    braced, bracketed,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token::Paren,
+    AngleBracketedGenericArguments, Attribute, Block, Expr, Ident, Item, ItemFn, LitInt, LitStr,
+    Meta, Path, Stmt, Token, Type, Visibility,
+};
+
+use crate::registry::{FunctionData, Mangling};
+
+/// Binary operator traits of `core::ops` accepted by `op = Trait`.
+pub(crate) const BINARY_OPS: [&str; 10] = [
+    "Add", "Sub", "Mul", "Div", "Rem", "BitAnd", "BitOr", "BitXor", "Shl", "Shr",
+];
+
+/// Function item with its body kept as tokens: the attributes only rewrite the signature, and parsing the statements
+/// of the body would be most of the work of every expansion.
+pub(crate) struct UnparsedFn(pub(crate) ItemFn);
+
+impl Parse for UnparsedFn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let sig = input.parse()?;
+        let body;
+        let brace_token = braced!(body in input);
+        let block = Block {
+            brace_token,
+            stmts: vec![Stmt::Item(Item::Verbatim(body.parse()?))],
+        };
+        Ok(Self(ItemFn {
+            attrs,
+            vis,
+            sig,
+            block: Box::new(block),
+        }))
+    }
+}
+
+/// Arguments accepted by the [`overload`](macro@crate::overload) attribute: an optional struct name with its generic
+/// arguments (or `Type as Trait`) followed by flags.
+#[derive(Default)]
+pub(crate) struct OverloadArgs {
+    pub(crate) struct_name: Option<Ident>,
+    /// Generic arguments of the type as written after its name, `<Phase1>` for an impl of `Builder<Phase1>`.
+    pub(crate) type_args: Option<AngleBracketedGenericArguments>,
+    /// Trait implemented by the struct in `Type as Trait`, for methods of a trait impl.
+    pub(crate) trait_path: Option<Path>,
+    /// `trait_def`: the method has a default body in the definition of the trait named in place of the type, and the
+    /// macro calls it on any type implementing the trait.
+    pub(crate) trait_def: bool,
+    pub(crate) replace: bool,
+    /// Leave the receiver out of the arity used to rename the method.
+    pub(crate) skip_self: bool,
+    /// Keep a function under the original name calling this overload.
+    pub(crate) default: bool,
+    /// Also generate a macro named after the type calling the set, for constructors.
+    pub(crate) ctor: bool,
+    /// Implement `Default` for the type by calling the zero-argument constructor.
+    pub(crate) derive_default: bool,
+    /// Implement `From` (or `TryFrom` for constructors returning a `Result`) for the type by calling the one-argument
+    /// constructor.
+    pub(crate) derive_from: bool,
+    /// `op = Add`: implement the operator trait for the types of the two arguments by calling the overload.
+    pub(crate) op: Option<Ident>,
+    /// Keep the renamed function in the documentation instead of hiding it.
+    pub(crate) doc: bool,
+    /// Lower the visibility of the function to the least visible overload already registered in the set.
+    pub(crate) inherit_vis: bool,
+    /// `into` or `into(2, 3)`: convert the arguments with `Into::into` in the macro, and add options of the given
+    /// arities packing their arguments into a tuple converted into the single parameter of the function.
+    pub(crate) into: Option<Vec<usize>>,
+    /// `place(0, 2)`: positions of the `&mut` parameters, after the receiver, whose arguments are places the macro
+    /// borrows mutably, so `fill!(buffer, 0)` passes `&mut buffer`.
+    pub(crate) places: Vec<usize>,
+    /// Call the method through a generated wrapper returning the receiver, for chaining builder methods.
+    pub(crate) chain: bool,
+    /// The overload belongs to a generic impl, whose type parameters can't be named outside of it.
+    pub(crate) generic: bool,
+    /// `generic(T, N)`: type and const parameters of the generic impl, placeholders for which the macro passes to the
+    /// type so they are inferred.
+    pub(crate) impl_params: Vec<Ident>,
+    /// Call the overload through a generated wrapper catching its panics.
+    pub(crate) catch_unwind: bool,
+    /// Tell the overload apart from others of the same arity by the length of its array parameter.
+    pub(crate) by_len: bool,
+    /// Tell the overload apart from others of the same arity by the types of literal arguments.
+    pub(crate) by_literal: bool,
+    /// Generate an argument enum and a dispatcher function for the set.
+    pub(crate) dispatch: bool,
+    /// Generate a table of function pointers to the overloads of the set.
+    pub(crate) table: bool,
+    /// Keep the set for a `macros!(local)` in the same block.
+    pub(crate) local: bool,
+    /// Rename the function and register it for the duplicate checks, but leave it out of the macro of its set.
+    pub(crate) no_macro: bool,
+    /// Generate a pyo3 function taking the arguments of the longer overloads as optional.
+    pub(crate) pyo3: bool,
+    /// Generate a wasm-bindgen function taking the arguments of the longer overloads as optional.
+    pub(crate) wasm: bool,
+    /// Export the macro of the set from the crate.
+    pub(crate) export: bool,
+    /// Leave the overload out of the exported macro.
+    pub(crate) internal: bool,
+    /// Add the overload to an extension trait calling the set with the method call syntax.
+    pub(crate) ext: bool,
+    /// Add the overload to an inherent method of the type calling the set with the arguments in a tuple.
+    pub(crate) inherent: bool,
+    /// `module = path`: module defining the overload, so the macros call it through `$crate::path` from anywhere.
+    pub(crate) module: Option<Path>,
+    /// Wrap the call of an unsafe overload in an `unsafe` block in the macro instead of leaving it to the caller.
+    pub(crate) wrap_unsafe: bool,
+    /// `alias = "name"`, once per alias: also register the overload in the set of the macro `name!`.
+    pub(crate) aliases: Vec<Ident>,
+    /// `extern_name = "name"`: also export the overload to C as `name_{arity}`.
+    pub(crate) extern_name: Option<Ident>,
+    /// `mangle = hidden`: rename the function to `__overfn_{name}_{arity}`, out of the way of handwritten functions.
+    pub(crate) hidden_name: bool,
+    /// `receiver = copy`: call the method on a copy of the receiver instead of borrowing or moving it.
+    pub(crate) copy_receiver: bool,
+    /// `receiver = method`: call the method of an inherent impl with the method call syntax instead of its qualified
+    /// path.
+    pub(crate) method_call: bool,
+    /// `unify_return = Enum`: wrap the result of every call of the set in the variant of its arity of an enum
+    /// generated with it.
+    pub(crate) unify_return: Option<Ident>,
+    /// `keywords` or `keywords(name = default, ..)`: let the macro call the overload with its arguments given by
+    /// parameter name, in any order, leaving out those with a default.
+    pub(crate) keywords: Option<Vec<(Ident, Expr)>>,
+    /// `optional_from = N`: also generate the overloads of the arities from `N` up, calling this one with
+    /// `Default::default()` for the parameters they leave out.
+    pub(crate) optional_from: Option<usize>,
+    /// `priority = N`: order of the option among the options of the same arity, the highest first.
+    pub(crate) priority: i32,
+    /// `set` or `set = name`: register through marker items for
+    /// [`define_overloads!`](macro@crate::define_overloads) instead of the global registry, optionally under a set
+    /// name different from the function name.
+    pub(crate) set: Option<Option<Ident>>,
+    /// Macro name given to [`overload_set`](macro@crate::overload_set), grouping overloads with other names or owners.
+    pub(crate) macro_name: Option<Ident>,
+}
+
+impl OverloadArgs {
+    /// Arguments of [`overload_set`](macro@crate::overload_set): the macro name as a string literal, followed by the
+    /// arguments accepted by [`overload`](macro@crate::overload).
+    pub(crate) fn parse_set(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<LitStr>()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        let mut args = input.parse::<Self>()?;
+        args.macro_name = Some(name.parse()?);
+        Ok(args)
+    }
+}
+
+impl Parse for OverloadArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        while !input.is_empty() {
+            let ident = input.call(Ident::parse_any)?;
+            match ident.to_string().as_str() {
+                "override" => args.replace = true,
+                "skip_self" => args.skip_self = true,
+                "doc" => args.doc = true,
+                "ctor" => args.ctor = true,
+                "derive_default" => args.derive_default = true,
+                "derive_from" => args.derive_from = true,
+                "default" => args.default = true,
+                "inherit_vis" => args.inherit_vis = true,
+                "chain" => args.chain = true,
+                "trait_def" => args.trait_def = true,
+                "catch_unwind" => args.catch_unwind = true,
+                "generic" if input.peek(Paren) => {
+                    let content;
+                    parenthesized!(content in input);
+                    let params = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                    args.generic = true;
+                    args.impl_params = params.into_iter().collect();
+                }
+                "generic" => args.generic = true,
+                "by_len" => args.by_len = true,
+                "by_literal" => args.by_literal = true,
+                "dispatch" => args.dispatch = true,
+                "table" => args.table = true,
+                "local" => args.local = true,
+                "no_macro" => args.no_macro = true,
+                "pyo3" => args.pyo3 = true,
+                "wasm" => args.wasm = true,
+                "export" => args.export = true,
+                "internal" => args.internal = true,
+                "ext" => args.ext = true,
+                "inherent" => args.inherent = true,
+                "wrap_unsafe" => args.wrap_unsafe = true,
+                "into" if input.peek(Paren) => {
+                    let content;
+                    parenthesized!(content in input);
+                    let arities = Punctuated::<LitInt, Token![,]>::parse_terminated(&content)?;
+                    args.into = Some(
+                        arities
+                            .iter()
+                            .map(LitInt::base10_parse)
+                            .collect::<syn::Result<_>>()?,
+                    );
+                }
+                "into" => args.into = Some(Vec::new()),
+                "place" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let positions = Punctuated::<LitInt, Token![,]>::parse_terminated(&content)?;
+                    args.places = positions
+                        .iter()
+                        .map(LitInt::base10_parse)
+                        .collect::<syn::Result<_>>()?;
+                }
+                "keywords" if input.peek(Paren) => {
+                    let content;
+                    parenthesized!(content in input);
+                    let defaults = content.parse_terminated(keyword, Token![,])?;
+                    args.keywords = Some(defaults.into_iter().collect());
+                }
+                "keywords" => args.keywords = Some(Vec::new()),
+                "unify_return" => {
+                    input.parse::<Token![=]>()?;
+                    args.unify_return = Some(input.parse()?);
+                }
+                "optional_from" => {
+                    input.parse::<Token![=]>()?;
+                    args.optional_from = Some(input.parse::<LitInt>()?.base10_parse()?);
+                }
+                "priority" => {
+                    input.parse::<Token![=]>()?;
+                    let negative = input.parse::<Option<Token![-]>>()?.is_some();
+                    let priority = input.parse::<LitInt>()?.base10_parse::<i32>()?;
+                    args.priority = match negative {
+                        true => -priority,
+                        false => priority,
+                    };
+                }
+                "receiver" => {
+                    input.parse::<Token![=]>()?;
+                    let mode = input.parse::<Ident>()?;
+                    match mode.to_string().as_str() {
+                        "copy" => args.copy_receiver = true,
+                        "method" => args.method_call = true,
+                        _ => {
+                            return Err(syn::Error::new(mode.span(), "expected `copy` or `method`"))
+                        }
+                    }
+                }
+                "op" if input.peek(Token![=]) => {
+                    input.parse::<Token![=]>()?;
+                    let op = input.parse::<Ident>()?;
+                    if !BINARY_OPS.contains(&op.to_string().as_str()) {
+                        return Err(syn::Error::new(
+                            op.span(),
+                            format!("expected one of {}", BINARY_OPS.join(", ")),
+                        ));
+                    }
+                    args.op = Some(op);
+                }
+                "alias" => {
+                    input.parse::<Token![=]>()?;
+                    args.aliases.push(input.parse::<LitStr>()?.parse()?);
+                }
+                "extern_name" => {
+                    input.parse::<Token![=]>()?;
+                    args.extern_name = Some(input.parse::<LitStr>()?.parse()?);
+                }
+                "mangle" => {
+                    input.parse::<Token![=]>()?;
+                    let scheme = input.parse::<Ident>()?;
+                    match scheme == "hidden" {
+                        true => args.hidden_name = true,
+                        false => return Err(syn::Error::new(scheme.span(), "expected `hidden`")),
+                    }
+                }
+                "module" => {
+                    input.parse::<Token![=]>()?;
+                    args.module = Some(module_path(input)?);
+                }
+                "set" if input.peek(Token![=]) => {
+                    input.parse::<Token![=]>()?;
+                    args.set = Some(Some(input.parse()?));
+                }
+                "set" => args.set = Some(None),
+                _ if args.struct_name.is_none() => {
+                    args.struct_name = Some(ident);
+                    if input.peek(Token![<]) {
+                        args.type_args = Some(input.parse()?);
+                    }
+                    if input.parse::<Option<Token![as]>>()?.is_some() {
+                        args.trait_path = Some(input.parse()?);
+                    }
+                }
+                _ => return Err(syn::Error::new(ident.span(), "unexpected argument")),
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Path of a module from the crate root, given as `shapes` or `crate::shapes`, without the `crate::`.
+fn module_path(input: ParseStream) -> syn::Result<Path> {
+    let mut path = input.parse::<Path>()?;
+    // `crate::shapes` and `shapes` both name the module from the crate root.
+    if path.leading_colon.is_none() && path.segments.len() > 1 && path.segments[0].ident == "crate"
+    {
+        path.segments = path.segments.into_iter().skip(1).collect();
+    }
+    let root = &path.segments[0].ident;
+    if path.leading_colon.is_some() || root == "crate" || root == "self" || root == "super" {
+        return Err(syn::Error::new(
+            root.span(),
+            "expected the path of the module from the crate root",
+        ));
+    }
+    Ok(path)
+}
+
+/// Arguments accepted by [`macros!()`](macro@crate::macros): `local` to generate the sets declared with
+/// `#[overload(local)]` instead of the others, `prefix = "ov_"` to prepend a prefix to the generated macros, `persist`
+/// to save the generated sets for other compilations of the package, `import = "mycrate"` to generate the sets saved by
+/// another crate instead, `docs` or `docs = "module"` to generate their documentation page, `prelude` or `prelude =
+/// "module"` to re-export their public items from a module, `vis = pub(crate)` to override the visibility of the
+/// macros, `module = path` to generate the overloads declared in a module under macro names prefixed with its path and
+/// `order = registration` to generate the macros in the order their sets were registered, optionally followed by a
+/// filter.
+pub(crate) struct MacrosArgs {
+    pub(crate) local: bool,
+    pub(crate) prefix: String,
+    pub(crate) persist: bool,
+    pub(crate) import: Option<Ident>,
+    /// Module of the documentation page of the generated sets.
+    pub(crate) docs: Option<Ident>,
+    /// Module re-exporting the public functions and exported macros of the generated sets.
+    pub(crate) prelude: Option<Ident>,
+    /// Visibility of the generated macros, instead of the visibility of the least visible overload of each set.
+    pub(crate) vis: Option<Visibility>,
+    /// Module whose overloads, declared with `module = path`, are generated apart under prefixed macro names.
+    pub(crate) module: Option<Path>,
+    /// Lint attributes of the generated macros and their re-exports given with `lints(..)`, instead of
+    /// `allow(unused_macros)` and `allow(unused_imports)`.
+    pub(crate) lints: Option<Vec<Meta>>,
+    /// Generate the macros in the order their sets were first registered instead of by name.
+    pub(crate) registration_order: bool,
+    pub(crate) filter: SetFilter,
+}
+
+impl MacrosArgs {
+    /// Name of the macro generated for the set `name`, after the prefix and the path of the module.
+    pub(crate) fn macro_name(&self, name: &str) -> String {
+        let module = self.module.iter().flat_map(|module| &module.segments);
+        let module = module
+            .map(|segment| format!("{}_", segment.ident))
+            .collect::<String>();
+        format!("{}{}{}", self.prefix, module, name)
+    }
+
+    /// Lint attributes given with `lints(..)`, `None` for the defaults.
+    pub(crate) fn lint_attrs(&self) -> Option<TokenStream> {
+        let lints = self.lints.as_ref()?;
+        Some(quote! { #(#[#lints])* })
+    }
+
+    /// Whether the set generating the macro `name` is generated by this invocation.
+    pub(crate) fn selects(&self, name: &str, functions: &[FunctionData]) -> bool {
+        let local = functions.iter().any(|data| data.local);
+        local == self.local && self.filter.selects(name)
+    }
+}
+
+impl Parse for MacrosArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut local = false;
+        let mut prefix = String::new();
+        let mut persist = false;
+        let mut import = None;
+        let mut docs = None;
+        let mut prelude = None;
+        let mut vis = None;
+        let mut module = None;
+        let mut lints = None;
+        let mut registration_order = false;
+        while input.peek(Ident) {
+            let ident = input.fork().parse::<Ident>()?;
+            if ident == "local" {
+                input.parse::<Ident>()?;
+                local = true;
+            } else if ident == "persist" {
+                input.parse::<Ident>()?;
+                persist = true;
+            } else if ident == "import" {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                import = Some(input.parse::<LitStr>()?.parse_with(Ident::parse_any)?);
+            } else if ident == "docs" {
+                input.parse::<Ident>()?;
+                docs = Some(match input.parse::<Option<Token![=]>>()? {
+                    Some(_) => input.parse::<LitStr>()?.parse::<Ident>()?,
+                    None => Ident::new("overloads", ident.span()),
+                });
+            } else if ident == "prelude" {
+                input.parse::<Ident>()?;
+                prelude = Some(match input.parse::<Option<Token![=]>>()? {
+                    Some(_) => input.parse::<LitStr>()?.parse::<Ident>()?,
+                    None => Ident::new("overfn_prelude", ident.span()),
+                });
+            } else if ident == "vis" {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                let parsed = input.parse::<Visibility>()?;
+                if let Visibility::Inherited = parsed {
+                    return Err(input.error(
+                        "expected `pub`, `pub(crate)`, `pub(super)`, `pub(in path)` or `pub(self)`",
+                    ));
+                }
+                vis = Some(parsed);
+            } else if ident == "module" {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                module = Some(module_path(input)?);
+            } else if ident == "lints" {
+                input.parse::<Ident>()?;
+                let content;
+                parenthesized!(content in input);
+                let attrs = Punctuated::<Meta, Token![,]>::parse_terminated(&content)?;
+                let levels = ["allow", "expect", "warn", "deny", "forbid"];
+                if let Some(attr) = attrs
+                    .iter()
+                    .find(|attr| !levels.iter().any(|level| attr.path().is_ident(level)))
+                {
+                    return Err(syn::Error::new_spanned(
+                        attr.path(),
+                        "expected a lint level: `allow`, `expect`, `warn`, `deny` or `forbid`",
+                    ));
+                }
+                lints = Some(attrs.into_iter().collect());
+            } else if ident == "order" {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                let order = input.parse::<Ident>()?;
+                registration_order = match order.to_string().as_str() {
+                    "registration" => true,
+                    "name" => false,
+                    _ => {
+                        return Err(syn::Error::new(
+                            order.span(),
+                            "expected `registration` or `name`",
+                        ))
+                    }
+                };
+            } else if ident == "prefix" {
+                input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                prefix = input.parse::<LitStr>()?.value();
+            } else {
+                break;
+            }
+            input.parse::<Option<Token![,]>>()?;
+        }
+        if let Some(krate) = import
+            .as_ref()
+            .filter(|_| local || persist || docs.is_some() || prelude.is_some() || module.is_some())
+        {
+            return Err(syn::Error::new(
+                krate.span(),
+                "`import` generates the sets of another crate and can't be combined with `local`, `persist`, \
+                 `docs`, `prelude` or `module`",
+            ));
+        }
+        if let Some(prelude) = prelude.as_ref().filter(|_| local) {
+            return Err(syn::Error::new(
+                prelude.span(),
+                "`prelude` re-exports the sets for other crates, which the block-scoped macros of `local` can't reach",
+            ));
+        }
+        Ok(Self {
+            local,
+            prefix,
+            persist,
+            import,
+            docs,
+            prelude,
+            vis,
+            module,
+            lints,
+            registration_order,
+            filter: input.parse()?,
+        })
+    }
+}
+
+/// Filter of [`macros!()`](macro@crate::macros): nothing to generate every registered set, `only(a, b)` to generate
+/// just the named sets or `except(a, b)` to generate all the others.
+pub(crate) enum SetFilter {
+    All,
+    Only(Vec<Ident>),
+    Except(Vec<Ident>),
+}
+
+impl SetFilter {
+    /// Whether the set generating the macro `name` is selected by the filter.
+    pub(crate) fn selects(&self, name: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Only(names) => names.iter().any(|ident| ident.unraw() == name),
+            Self::Except(names) => names.iter().all(|ident| ident.unraw() != name),
+        }
+    }
+}
+
+impl Parse for SetFilter {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Self::All);
+        }
+        let filter = input.parse::<Ident>()?;
+        let content;
+        parenthesized!(content in input);
+        let names = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+        input.parse::<Option<Token![,]>>()?;
+        match filter.to_string().as_str() {
+            "only" => Ok(Self::Only(names)),
+            "except" => Ok(Self::Except(names)),
+            _ => Err(syn::Error::new(
+                filter.span(),
+                "expected `local`, `prefix = \"..\"`, `persist`, `import = \"..\"`, `docs`, `prelude`, `vis = ..`, \
+                 `module = ..`, `lints(..)`, `order = ..`, `only(...)` or `except(...)`",
+            )),
+        }
+    }
+}
+
+/// Set collected by [`define_overloads!`](macro@crate::define_overloads): a free function set, an associated function
+/// (`Type::name`) or a method (`Type.name`, followed by `, skip_self` when its overloads don't count the receiver).
+pub(crate) enum SetPath {
+    Free(Ident),
+    Static(Ident, Ident),
+    Instance(Ident, Ident, bool),
+}
+
+impl Parse for SetPath {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let first = input.parse()?;
+        let set = if input.parse::<Option<Token![::]>>()?.is_some() {
+            Self::Static(first, input.parse()?)
+        } else if input.parse::<Option<Token![.]>>()?.is_some() {
+            Self::Instance(first, input.parse()?, false)
+        } else {
+            Self::Free(first)
+        };
+        match set {
+            Self::Instance(ty, ident, _) if input.parse::<Option<Token![,]>>()?.is_some() => {
+                let flag = input.parse::<Ident>()?;
+                match flag == "skip_self" {
+                    true => Ok(Self::Instance(ty, ident, true)),
+                    false => Err(syn::Error::new(flag.span(), "expected `skip_self`")),
+                }
+            }
+            set => Ok(set),
+        }
+    }
+}
+
+/// Settings of [`config!`](macro@crate::config): `mangle = "template"`, with `{name}` and `{arity}` placeholders, and
+/// `inline`.
+pub(crate) struct ConfigArgs {
+    pub(crate) mangle: Option<Mangling>,
+    /// Mark the overloads with `#[inline(always)]` and check that the macros call them directly.
+    pub(crate) inline: bool,
+}
+
+impl Parse for ConfigArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Self {
+            mangle: None,
+            inline: false,
+        };
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            match ident.to_string().as_str() {
+                "mangle" => {
+                    input.parse::<Token![=]>()?;
+                    let template = input.parse::<LitStr>()?;
+                    let mangling = Mangling(template.value());
+                    let placeholders =
+                        mangling.0.contains("{name}") && mangling.0.contains("{arity}");
+                    let sample = mangling.apply("name", 0);
+                    if !placeholders || syn::parse_str::<Ident>(&sample).is_err() {
+                        return Err(syn::Error::new(
+                            template.span(),
+                            "expected a template of an identifier with `{name}` and `{arity}`, such as \
+                             \"__{name}_ov{arity}\"",
+                        ));
+                    }
+                    args.mangle = Some(mangling);
+                }
+                "inline" => args.inline = true,
+                _ => return Err(syn::Error::new(ident.span(), "unexpected argument")),
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Cases checked by [`test_suite!`](macro@crate::test_suite): the name of the set followed by
+/// `cases = { (args) => expected, .. }`.
+pub(crate) struct TestSuite {
+    pub(crate) name: Ident,
+    pub(crate) cases: Vec<(Punctuated<Expr, Token![,]>, Expr)>,
+}
+
+impl Parse for TestSuite {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key = input.parse::<Ident>()?;
+        if key != "cases" {
+            return Err(syn::Error::new(key.span(), "expected `cases = { .. }`"));
+        }
+        input.parse::<Token![=]>()?;
+        let content;
+        braced!(content in input);
+        let mut cases = Vec::new();
+        while !content.is_empty() {
+            let args;
+            parenthesized!(args in content);
+            let args = Punctuated::parse_terminated(&args)?;
+            content.parse::<Token![=>]>()?;
+            cases.push((args, content.parse()?));
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { name, cases })
+    }
+}
+
+/// Receiver expanded by the qualified calls of a macro: `&` or `&mut` followed by the receiver expression.
+pub(crate) struct BorrowedReceiver {
+    pub(crate) mutable: bool,
+    pub(crate) receiver: Expr,
+}
+
+impl Parse for BorrowedReceiver {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![&]>()?;
+        Ok(Self {
+            mutable: input.parse::<Option<Token![mut]>>()?.is_some(),
+            receiver: input.parse()?,
+        })
+    }
+}
+
+/// Argument given by parameter name: `name = value`.
+fn keyword(input: ParseStream) -> syn::Result<(Ident, Expr)> {
+    let name = input.parse()?;
+    input.parse::<Token![=]>()?;
+    Ok((name, input.parse()?))
+}
+
+/// Overload a keyword call can pick: `{ path } [params]`, where the parameters with a default are followed by
+/// `= default`, preceded by `unsafe` when the macro wraps its calls in an `unsafe` block.
+pub(crate) struct KeywordCandidate {
+    pub(crate) wrapped: bool,
+    pub(crate) path: TokenStream,
+    pub(crate) params: Vec<(Ident, Option<Expr>)>,
+}
+
+/// Call expanded by the keyword option of a macro: the name of the set, `;`, the overloads marked with `keywords`,
+/// `;` and the arguments given by name.
+pub(crate) struct KeywordCall {
+    pub(crate) name: Ident,
+    pub(crate) candidates: Vec<KeywordCandidate>,
+    pub(crate) args: Vec<(Ident, Expr)>,
+}
+
+impl Parse for KeywordCall {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let mut candidates = Vec::new();
+        while !input.peek(Token![;]) {
+            let wrapped = input.parse::<Option<Token![unsafe]>>()?.is_some();
+            let (path, params);
+            braced!(path in input);
+            bracketed!(params in input);
+            let params = params.parse_terminated(
+                |input| {
+                    let name = input.parse()?;
+                    match input.parse::<Option<Token![=]>>()? {
+                        Some(_) => Ok((name, Some(input.parse()?))),
+                        None => Ok((name, None)),
+                    }
+                },
+                Token![,],
+            )?;
+            candidates.push(KeywordCandidate {
+                wrapped,
+                path: path.parse()?,
+                params: params.into_iter().collect(),
+            });
+        }
+        input.parse::<Token![;]>()?;
+        let args = input.parse_terminated(keyword, Token![,])?;
+        Ok(Self {
+            name,
+            candidates,
+            args: args.into_iter().collect(),
+        })
+    }
+}
+
+/// Call expanded by the literal options of a macro: the overloads of the arity marked with `by_literal`, each as
+/// `{ path } [types]`, followed by `;` and the literal arguments.
+pub(crate) struct LiteralCall {
+    pub(crate) candidates: Vec<(TokenStream, Vec<Type>)>,
+    pub(crate) args: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for LiteralCall {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut candidates = Vec::new();
+        while !input.peek(Token![;]) {
+            let (path, types);
+            braced!(path in input);
+            bracketed!(types in input);
+            let types = Punctuated::<Type, Token![,]>::parse_terminated(&types)?;
+            candidates.push((path.parse()?, types.into_iter().collect()));
+        }
+        input.parse::<Token![;]>()?;
+        Ok(Self {
+            candidates,
+            args: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
@@ -0,0 +1,2841 @@
+//! Code generated for the overloads: renamed function extras and the `macro_rules!` dispatching between them.
+use std::{
+    borrow::Cow,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt, spanned::Spanned, Expr, FnArg, Ident, ItemFn, Lit, PathArguments, ReturnType,
+    Type, UnOp, Visibility,
+};
+
+use crate::{
+    args::{BorrowedReceiver, KeywordCall, KeywordCandidate, LiteralCall, SetPath, TestSuite},
+    registry::{ArgType, Convert, FunctionData, Reach, ReceiverKind, Safety},
+};
+
+/// Highest arity handled by the macros generated through [`define_overloads!`](macro@crate::define_overloads), which
+/// can't know which arities were registered.
+const MAX_SET_ARITY: usize = 16;
+
+/// Helper macro `__overfn_{set}_{arity}` forwarding to a free function overloaded with `#[overload(set)]`. The macro
+/// generated by [`define_overloads!`](macro@crate::define_overloads) calls it for the matching arity, so a missing
+/// overload fails to resolve the helper and a duplicated one clashes with its re-export.
+pub(crate) fn set_marker(set: &Ident, function: &Ident, n_args: usize) -> TokenStream {
+    let helper = format_ident!("__overfn_{}_{}", set, n_args);
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #helper {
+            ($($arg:expr),*) => { #function($($arg),*) };
+        }
+        #[allow(unused_imports)]
+        pub(crate) use #helper;
+    }
+}
+
+/// Hidden `{name}_chain` sibling of a method taking `&self` or `&mut self` and returning `()`, which calls it and
+/// returns the receiver so every overload of a builder set can be chained the same way.
+pub(crate) fn chain_wrapper(function: &ItemFn, target: &Ident) -> syn::Result<ItemFn> {
+    let sig = &function.sig;
+    let receiver = match sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) if receiver.colon_token.is_none() => receiver,
+        _ => {
+            return Err(syn::Error::new(
+                sig.ident.span(),
+                "`chain` needs a method taking `&self` or `&mut self`",
+            ))
+        }
+    };
+    let output =
+        match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => quote! { &mut Self },
+            (Some(_), None) => quote! { &Self },
+            (None, _) => return Err(syn::Error::new(
+                receiver.self_token.span,
+                "`chain` doesn't apply to methods taking `self` by value, return `Self` instead",
+            )),
+        };
+    if !matches!(sig.output, ReturnType::Default) {
+        return Err(syn::Error::new(
+            sig.output.span(),
+            "`chain` wraps methods returning `()`, the returned value would be discarded",
+        ));
+    }
+    let wrapper = format_ident!("{}_chain", target, span = target.span());
+    companion(function, target, &wrapper, "chain", true, output, |call| {
+        quote! {
+            #call;
+            self
+        }
+    })
+}
+
+/// Hidden `{name}_catch_unwind` sibling of an overload, which calls it inside [`std::panic::catch_unwind`] and returns
+/// the panic payload as an error instead of unwinding into the caller.
+pub(crate) fn catch_unwind_wrapper(
+    function: &ItemFn,
+    target: &Ident,
+    associated: bool,
+) -> syn::Result<ItemFn> {
+    let output = match &function.sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => ty.to_token_stream(),
+    };
+    let output = quote! { ::std::thread::Result<#output> };
+    let wrapper = format_ident!("{}_catch_unwind", target, span = target.span());
+    companion(
+        function,
+        target,
+        &wrapper,
+        "catch_unwind",
+        associated,
+        output,
+        |call| {
+            quote! {
+                ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| #call))
+            }
+        },
+    )
+}
+
+/// Function under the original name of an overload marked with `default`, calling it for the callers that haven't
+/// moved to the macro. It keeps the documentation and the deprecation of the overload.
+pub(crate) fn default_alias(
+    function: &ItemFn,
+    target: &Ident,
+    associated: bool,
+) -> syn::Result<ItemFn> {
+    let output = match &function.sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => ty.to_token_stream(),
+    };
+    let mut alias = companion(
+        function,
+        target,
+        &function.sig.ident,
+        "default",
+        associated,
+        output,
+        |call| call,
+    )?;
+    let docs = function
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("deprecated"));
+    alias.attrs = syn::parse_quote! { #(#docs)* #[inline] #[allow(deprecated)] };
+    Ok(alias)
+}
+
+/// `extern "C"` function exported unmangled as `shim`, calling an overload marked with `extern_name` so C code can
+/// call every overload of the set under a predictable symbol. It keeps the documentation of the overload.
+pub(crate) fn extern_shim(
+    function: &ItemFn,
+    target: &Ident,
+    shim: &Ident,
+    associated: bool,
+) -> syn::Result<ItemFn> {
+    let sig = &function.sig;
+    if let Some(FnArg::Receiver(receiver)) = sig.inputs.first() {
+        return Err(syn::Error::new(
+            receiver.self_token.span,
+            "`extern_name` only exports functions without receiver",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "`extern_name` can't export generic functions, which have no single symbol",
+        ));
+    }
+    let output = match &sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => ty.to_token_stream(),
+    };
+    let mut shim = companion(
+        function,
+        target,
+        shim,
+        "extern_name",
+        associated,
+        output,
+        |call| call,
+    )?;
+    let docs = function
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"));
+    shim.attrs = syn::parse_quote! { #(#docs)* #[unsafe(no_mangle)] #[allow(deprecated)] };
+    shim.vis = syn::parse_quote! { pub };
+    shim.sig.abi = Some(syn::parse_quote! { extern "C" });
+    Ok(shim)
+}
+
+/// Functions of the lower arities of an overload marked with `optional_from = from`, named like it so the attribute
+/// renames each one after its arity, calling the overload renamed `target` with `Default::default()` for the
+/// parameters they leave out. They keep the parameter names, documentation, `cfg` and deprecation of the overload.
+pub(crate) fn optional_delegates(
+    function: &ItemFn,
+    target: &Ident,
+    from: usize,
+    associated: bool,
+) -> syn::Result<Vec<ItemFn>> {
+    let sig = &function.sig;
+    let receiver = sig
+        .inputs
+        .first()
+        .filter(|input| matches!(input, FnArg::Receiver(_)));
+    let params = sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(arg) => Some(arg),
+            FnArg::Receiver(_) => None,
+        })
+        .enumerate()
+        .map(|(i, arg)| match &*arg.pat {
+            syn::Pat::Ident(pat) => (pat.ident.clone(), &arg.ty),
+            _ => (format_ident!("arg_{}", i), &arg.ty),
+        })
+        .collect::<Vec<_>>();
+    if from >= params.len() {
+        return Err(syn::Error::new(
+            sig.ident.span(),
+            format!(
+                "`optional_from = {}` leaves no parameter out of the {} of the function",
+                from,
+                params.len()
+            ),
+        ));
+    }
+    let attrs = function.attrs.iter().filter(|attr| {
+        ["doc", "cfg", "deprecated"]
+            .iter()
+            .any(|name| attr.path().is_ident(name))
+    });
+    let attrs = quote! { #(#attrs)* };
+    let vis = &function.vis;
+    let (constness, asyncness, unsafety, abi) =
+        (&sig.constness, &sig.asyncness, &sig.unsafety, &sig.abi);
+    let (ident, generics, output) = (&sig.ident, &sig.generics, &sig.output);
+    let where_clause = &generics.where_clause;
+    (from..params.len())
+        .map(|arity| {
+            let kept = &params[..arity];
+            let names = kept.iter().map(|(name, _)| name);
+            let defaults =
+                (arity..params.len()).map(|_| quote! { ::core::default::Default::default() });
+            let args = quote! { #(#names,)* #(#defaults),* };
+            let call = match (receiver, associated) {
+                (Some(_), _) => quote! { self.#target(#args) },
+                (None, true) => quote! { Self::#target(#args) },
+                (None, false) => quote! { #target(#args) },
+            };
+            let call = match unsafety {
+                Some(_) => quote! { unsafe { #call } },
+                None => call,
+            };
+            let call = match asyncness {
+                Some(_) => quote! { (#call).await },
+                None => call,
+            };
+            let inputs = receiver
+                .map(ToTokens::to_token_stream)
+                .into_iter()
+                .chain(kept.iter().map(|(name, ty)| quote! { #name: #ty }));
+            syn::parse2(quote! {
+                #attrs
+                #[allow(deprecated)]
+                #vis #constness #asyncness #unsafety #abi fn #ident #generics(#(#inputs),*) #output
+                #where_clause
+                {
+                    #call
+                }
+            })
+        })
+        .collect()
+}
+
+/// Hidden sibling `wrapper` of an overload taking the same arguments and returning `output`, whose body is built
+/// around the call of the overload. `associated` calls it through `Self` when it has no receiver.
+fn companion(
+    function: &ItemFn,
+    target: &Ident,
+    wrapper: &Ident,
+    feature: &str,
+    associated: bool,
+    output: TokenStream,
+    body: impl FnOnce(TokenStream) -> TokenStream,
+) -> syn::Result<ItemFn> {
+    let sig = &function.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span,
+            format!("`{}` doesn't support async functions", feature),
+        ));
+    }
+
+    let receiver = sig
+        .inputs
+        .first()
+        .filter(|input| matches!(input, FnArg::Receiver(_)));
+    let (names, types): (Vec<_>, Vec<_>) = sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(arg) => Some(&arg.ty),
+            FnArg::Receiver(_) => None,
+        })
+        .enumerate()
+        .map(|(i, ty)| (format_ident!("arg_{}", i), ty))
+        .unzip();
+    let call = match (receiver, associated) {
+        (Some(_), _) => quote! { self.#target(#(#names),*) },
+        (None, true) => quote! { Self::#target(#(#names),*) },
+        (None, false) => quote! { #target(#(#names),*) },
+    };
+    let call = match sig.unsafety {
+        Some(_) => quote! { unsafe { #call } },
+        None => call,
+    };
+    let body = body(call);
+    let vis = &function.vis;
+    let (unsafety, abi) = (&sig.unsafety, &sig.abi);
+    let generics = &sig.generics;
+    let where_clause = &generics.where_clause;
+    let inputs = receiver.map(ToTokens::to_token_stream).into_iter().chain(
+        names
+            .iter()
+            .zip(&types)
+            .map(|(name, ty)| quote! { #name: #ty }),
+    );
+    // The wrapper warns its callers like the overload, without warning about its own call.
+    let deprecated = function
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("deprecated"));
+    Ok(syn::parse_quote! {
+        #[doc(hidden)]
+        #[inline]
+        #(#deprecated)*
+        #[allow(deprecated)]
+        #vis #unsafety #abi fn #wrapper #generics(#(#inputs),*) -> #output
+        #where_clause
+        {
+            #body
+        }
+    })
+}
+
+/// Documentation attributes of a renamed function: a search alias for the original name, plus either
+/// `#[doc(hidden)]` or, when `doc` is requested on a public overload, a section describing how to call it so rustdoc
+/// presents the overloads as members of the same family.
+pub(crate) fn family_docs(
+    ident: &Ident,
+    n_args: usize,
+    macro_ident: &str,
+    doc: bool,
+    public: bool,
+) -> Vec<syn::Attribute> {
+    let alias = ident.to_string();
+    let mut attrs = vec![syn::parse_quote! { #[doc(alias = #alias)] }];
+    if !doc {
+        attrs.push(syn::parse_quote! { #[doc(hidden)] });
+        return attrs;
+    }
+    if public {
+        let header = format!(" # Overloads of `{}`", ident);
+        let body = format!(
+            " Overload of `{}` taking {} argument(s). Call it through the `{}!` macro to select the overload by \
+             the number of arguments.",
+            ident, n_args, macro_ident
+        );
+        attrs.extend([
+            syn::parse_quote! { #[doc = ""] },
+            syn::parse_quote! { #[doc = #header] },
+            syn::parse_quote! { #[doc = ""] },
+            syn::parse_quote! { #[doc = #body] },
+        ]);
+    }
+    attrs
+}
+
+/// Module generated by `macros!(docs)` for rustdoc alone, with a function named after each set standing in for its
+/// macro and documenting the signature of every overload, by arity.
+pub(crate) fn doc_page<'a>(
+    module: &Ident,
+    sets: impl Iterator<Item = (String, &'a [FunctionData])>,
+) -> TokenStream {
+    let facades = sets.map(|(name, functions)| {
+        let mut docs = vec![
+            format!(
+                " Overloads of the `{}!` macro, selected by the arguments of the call:",
+                name
+            ),
+            String::new(),
+        ];
+        for data in functions {
+            let receiver = match (&data.arg_type, data.receiver) {
+                (ArgType::Instance(ty) | ArgType::Trait(ty, _), Some(kind)) => {
+                    let reference = match kind {
+                        ReceiverKind::Value => "",
+                        ReceiverKind::Ref => "&",
+                        ReceiverKind::Mut => "&mut ",
+                    };
+                    let ty = match data.trait_def {
+                        true => format!("impl {}", tidy(ty)),
+                        false => tidy(ty),
+                    };
+                    Some(format!("self: {}{}", reference, ty))
+                }
+                _ => None,
+            };
+            let args = receiver
+                .into_iter()
+                .chain(
+                    data.arg_names
+                        .iter()
+                        .zip(&data.arg_types)
+                        .map(|(name, ty)| format!("{}: {}", name, tidy(ty))),
+                )
+                .collect::<Vec<_>>()
+                .join(", ");
+            let output = match &data.output {
+                Some(output) => format!(" -> {}", tidy(output)),
+                None => String::new(),
+            };
+            let summary = match &data.summary {
+                Some(summary) => format!(": {}", summary),
+                None => String::new(),
+            };
+            docs.push(format!(
+                " - `{}!({}){}`, calling `{}`{}",
+                name,
+                args,
+                output,
+                function_path(data),
+                summary
+            ));
+        }
+        let ident = set_ident(&name);
+        quote! {
+            #(#[doc = #docs])*
+            pub fn #ident() {}
+        }
+    });
+    let header = " Overload sets generated by `macros!()`, listing the overloads of each macro. The functions only \
+                  exist in the documentation and can't be called.";
+    quote! {
+        #[cfg(doc)]
+        #[doc = #header]
+        #[allow(non_snake_case)]
+        pub mod #module {
+            #(#facades)*
+        }
+    }
+}
+
+/// Module generated by `macros!(prelude)` next to the macros, re-exporting the `pub` free functions of the sets and the
+/// exported macros, so `use mylib::overfn_prelude::*;` brings every overload into scope. Functions are taken from the
+/// module of the invocation, or from their `module = path`, and exported macros from the crate root, where
+/// `#[macro_export]` puts them.
+pub(crate) fn prelude<'a>(
+    module: &Ident,
+    sets: impl Iterator<Item = (String, &'a [FunctionData])>,
+    export: bool,
+) -> TokenStream {
+    let mut functions = Vec::new();
+    let mut macros = Vec::new();
+    for (name, members) in sets {
+        for data in members {
+            // Aliases can put an overload in several sets, and `into` adapters share the name of their function.
+            let public = data.arg_type == ArgType::Other && data.reach == Reach::Public;
+            if public && !functions.iter().any(|(other, _)| *other == data.name) {
+                let path = match &data.module {
+                    Some(_) => module_path(data, &quote! { crate:: }),
+                    None => quote! { super:: },
+                };
+                let func = format_ident!("{}", data.name);
+                functions.push((data.name.clone(), quote! { #path #func }));
+            }
+        }
+        if export || members.iter().any(|data| data.export) {
+            macros.push(set_ident(&name));
+        }
+    }
+    let functions = functions.into_iter().map(|(_, path)| path);
+    quote! {
+        /// Public overloads and exported macros generated by `macros!()`, for glob imports.
+        pub mod #module {
+            #(pub use #functions;)*
+            #(pub use super::#macros;)*
+        }
+    }
+}
+
+/// Path of the renamed overload as written to call it, `Type::name` for methods.
+fn function_path(data: &FunctionData) -> String {
+    match &data.arg_type {
+        ArgType::Struct(ty) | ArgType::Instance(ty) => match &data.type_args {
+            Some(args) => format!("{}::{}::{}", tidy(ty), tidy(args), data.name),
+            None => format!("{}::{}", tidy(ty), data.name),
+        },
+        ArgType::Trait(ty, path) => format!(
+            "<{}{} as {}>::{}",
+            tidy(ty),
+            data.type_args.as_deref().map(tidy).unwrap_or_default(),
+            tidy(path),
+            data.name
+        ),
+        ArgType::Other => data.name.clone(),
+    }
+}
+
+/// Array of `(arity, path)` pairs listing the overloads of a set for [`overloads_of!`](macro@crate::overloads_of),
+/// counting the receiver of methods in the arity as the macro does.
+pub(crate) fn overloads_of(functions: &[FunctionData]) -> TokenStream {
+    let entries = functions.iter().map(|data| {
+        let n_args = data.n_args;
+        let path = function_path(data);
+        quote! { (#n_args, #path) }
+    });
+    quote! { [#(#entries),*] }
+}
+
+/// Tokens printed back as code, without the spaces `to_string` leaves around punctuation.
+pub(crate) fn tidy(tokens: &str) -> String {
+    let mut tidy = tokens.to_string();
+    for (spaced, tight) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        ("& ", "&"),
+        (" ,", ","),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+    ] {
+        tidy = tidy.replace(spaced, tight);
+    }
+    tidy
+}
+
+/// Macro dispatching between the overloads of a set registered for [`macros!()`](macro@crate::macros), as visible as
+/// its least visible overload unless `vis` is given with `macros!(vis = ..)`, where `pub` exports it.
+pub(crate) fn overload_macro(
+    name: &str,
+    functions: &[FunctionData],
+    vis: Option<&Visibility>,
+    lints: Option<&TokenStream>,
+) -> TokenStream {
+    let functions = &*unified(functions);
+    let mut options = keyword_options(&set_ident(name), functions);
+    options.extend(literal_options(functions));
+    let partial = partial_options(&set_ident(name), functions.iter(), &quote! {});
+    options.extend(turbofish_first(
+        functions.iter().filter(|data| data.array_len.is_none()),
+        &quote! {},
+        partial,
+    ));
+
+    let least = functions
+        .iter()
+        .min_by_key(|data| data.reach)
+        .expect("registered sets have at least one overload");
+    let export = matches!(vis, Some(Visibility::Public(_)));
+    let overridden = vis.is_some();
+    let vis = match vis {
+        Some(Visibility::Restricted(vis)) if vis.path.is_ident("self") => quote! {},
+        Some(vis @ Visibility::Restricted(_)) => quote! { #vis },
+        _ => match least.reach {
+            Reach::Private | Reach::Restricted => quote! {},
+            Reach::Crate | Reach::Public => quote! { pub(crate) },
+        },
+    };
+    let name = set_ident(name);
+    let exported = exported_macro(&name, functions, export);
+    // Exported sets warn about their own members instead, the local macro isn't re-exported.
+    let mismatch = functions
+        .iter()
+        .any(|data| data.reach != least.reach && exported.is_none() && !overridden)
+        .then(|| {
+            let members = functions
+                .iter()
+                .map(|data| match data.vis.is_empty() {
+                    true => format!("`{}` is private", data.name),
+                    false => format!("`{}` is `{}`", data.name, data.vis),
+                })
+                .collect::<Vec<_>>();
+            warning(&format!(
+                "overloads of `{}` have different visibilities ({}); the macro is only as visible as `{}`",
+                name,
+                members.join(", "),
+                least.name
+            ))
+        });
+
+    let instance = functions.iter().any(|data| data.receiver.is_some());
+    let (by_len, unnameable) = by_len_options(&name, functions);
+    options.extend(by_len);
+    let mut spread = arity_options(functions.iter());
+    spread.extend(literal_spread_options(&name, instance));
+    spread.extend(splat_options(&name, functions.iter(), instance));
+    // The spread trait returns the result of the overload as is.
+    let (typed_spread, spread_trait) = match instance || least.unify_return.is_some() {
+        true => (quote! {}, quote! {}),
+        false => typed_spread(&name, functions),
+    };
+    let unify = unified_enum(&name, functions, least);
+    let inline_checks = inline_checks(functions);
+    // A `default` alias already takes the name of the set in the value namespace.
+    #[cfg(feature = "fn_traits")]
+    let callable = match instance || functions.iter().any(|data| data.alias) {
+        true => quote! {},
+        false => callable_struct(&name, functions, least),
+    };
+    #[cfg(not(feature = "fn_traits"))]
+    let callable = quote! {};
+    #[cfg(feature = "runtime")]
+    let runtime = match instance {
+        true => quote! {},
+        false => runtime_dispatcher(&name, functions, least),
+    };
+    #[cfg(not(feature = "runtime"))]
+    let runtime = quote! {};
+    let extension = functions
+        .iter()
+        .any(|data| data.ext)
+        .then(|| method_syntax(&name, functions, least, false));
+    let inherent = functions
+        .iter()
+        .any(|data| data.inherent)
+        .then(|| method_syntax(&name, functions, least, true));
+    let dispatcher = functions
+        .iter()
+        .any(|data| data.dispatch)
+        .then(|| dispatcher(&name, functions, least));
+    let table = functions
+        .iter()
+        .any(|data| data.table)
+        .then(|| overload_table(&name, functions, least));
+    let pyo3 = functions
+        .iter()
+        .any(|data| data.pyo3)
+        .then(|| pyo3_function(&name, functions, least));
+    let wasm = functions
+        .iter()
+        .any(|data| data.wasm)
+        .then(|| wasm_function(&name, functions, least));
+    let ctor = functions
+        .iter()
+        .find_map(|data| match &data.arg_type {
+            ArgType::Struct(ty) if data.ctor => Some(ty),
+            _ => None,
+        })
+        .map(|ty| ctor_macro(&name, ty, &vis, lints));
+    // Packing adapters of `into` share the flags of their function, which gets the impls.
+    let derived = functions
+        .iter()
+        .filter(|data| data.convert != Convert::Pack)
+        .map(|data| {
+            let default = data.derive_default.then(|| default_impl(data));
+            let from = data.derive_from.then(|| from_impl(data));
+            let op = data.op.as_ref().map(|op| op_impl(data, op));
+            quote! { #default #from #op }
+        });
+    // The re-export imports every namespace, so next to a callable struct or a free function keeping the name of the
+    // set the macro needs a name of its own. Exported sets are re-exported under the name of the exported macro
+    // instead, while the local one stays in textual scope.
+    let aliased = functions
+        .iter()
+        .any(|data| data.alias && data.arg_type == ArgType::Other);
+    let (macro_name, local_use) = match (&exported, callable.is_empty() && !aliased) {
+        (Some(_), _) => (name.clone(), quote! {}),
+        (None, true) => (name.clone(), quote! { #vis use #name; }),
+        (None, false) => {
+            let macro_name = format_ident!("__overfn_macro_{}", name);
+            let local_use = quote! { #vis use #macro_name as #name; };
+            (macro_name, local_use)
+        }
+    };
+    let arity_error = arity_error(&name, functions.iter());
+    let (macro_lints, use_lints) = lint_attrs(lints);
+    quote! {
+        #mismatch
+        #unnameable
+        #unify
+        #inline_checks
+        #spread_trait
+        #callable
+        #runtime
+        #extension
+        #inherent
+        #dispatcher
+        #table
+        #pyo3
+        #wasm
+        #macro_lints
+        macro_rules! #macro_name {
+            #(#spread;)*
+            #typed_spread
+            #(#options;)*
+            #arity_error
+        }
+        #use_lints
+        #local_use
+        #ctor
+        #(#derived)*
+        #exported
+    }
+}
+
+/// Impl of the operator trait `op` of `core::ops` for the type of the first argument of a two-argument function marked
+/// with `op = Trait`, with the type of the second as the right-hand side, calling the function.
+fn op_impl(data: &FunctionData, op: &str) -> TokenStream {
+    let [lhs, rhs] = [&data.arg_types[0], &data.arg_types[1]].map(|ty| {
+        ty.parse::<TokenStream>()
+            .expect("argument types are recorded from tokens")
+    });
+    let output = data
+        .output
+        .as_deref()
+        .expect("`op` is only accepted on functions returning a value")
+        .parse::<TokenStream>()
+        .expect("return types are recorded from tokens");
+    let func = format_ident!("{}", data.name);
+    let method = format_ident!("{}", op.to_lowercase());
+    let op = format_ident!("{}", op);
+    quote! {
+        #[allow(deprecated)]
+        impl ::core::ops::#op<#rhs> for #lhs {
+            type Output = #output;
+            fn #method(self, rhs: #rhs) -> Self::Output {
+                #func(self, rhs)
+            }
+        }
+    }
+}
+
+/// Type of the impl of an associated function as named in the impls derived from it, with its generic arguments.
+fn self_type(data: &FunctionData, ty: &str) -> TokenStream {
+    let ty = format_ident!("{}", ty);
+    let module = module_path(data, &quote! { crate:: });
+    let type_args = data.type_args.as_ref().map(|args| {
+        args.parse::<TokenStream>()
+            .expect("generic arguments are recorded from tokens")
+    });
+    quote! { #module #ty #type_args }
+}
+
+/// `From` impl of the type of a one-argument constructor marked with `derive_from`, calling it, or `TryFrom` with the
+/// error type of the constructor when it returns a `Result`.
+fn from_impl(data: &FunctionData) -> TokenStream {
+    let ArgType::Struct(ty) = &data.arg_type else {
+        unreachable!("`derive_from` is only accepted on associated functions");
+    };
+    let ty = self_type(data, ty);
+    let func = format_ident!("{}", data.name);
+    let arg = data.arg_types[0]
+        .parse::<TokenStream>()
+        .expect("argument types are recorded from tokens");
+    let output = data
+        .output
+        .as_deref()
+        .and_then(|output| syn::parse_str::<Type>(output).ok());
+    let error = output.as_ref().and_then(|output| {
+        let Type::Path(path) = output else {
+            return None;
+        };
+        let last = path.path.segments.last()?;
+        let PathArguments::AngleBracketed(args) = &last.arguments else {
+            return None;
+        };
+        match (last.ident == "Result", args.args.len()) {
+            (true, 2) => args.args.last(),
+            _ => None,
+        }
+    });
+    match error {
+        Some(error) => quote! {
+            #[allow(deprecated)]
+            impl ::core::convert::TryFrom<#arg> for #ty {
+                type Error = #error;
+                fn try_from(value: #arg) -> ::core::result::Result<Self, Self::Error> {
+                    Self::#func(value)
+                }
+            }
+        },
+        None => quote! {
+            #[allow(deprecated)]
+            impl ::core::convert::From<#arg> for #ty {
+                fn from(value: #arg) -> Self {
+                    Self::#func(value)
+                }
+            }
+        },
+    }
+}
+
+/// `Default` impl of the type of a zero-argument constructor marked with `derive_default`, calling it.
+fn default_impl(data: &FunctionData) -> TokenStream {
+    let ArgType::Struct(ty) = &data.arg_type else {
+        unreachable!("`derive_default` is only accepted on associated functions");
+    };
+    let ty = self_type(data, ty);
+    let func = format_ident!("{}", data.name);
+    quote! {
+        #[allow(deprecated)]
+        impl ::core::default::Default for #ty {
+            fn default() -> Self {
+                Self::#func()
+            }
+        }
+    }
+}
+
+/// Macro named after the type of a set marked with `ctor`, forwarding to the macro of the set so `Test!(2, 3)` calls
+/// `Test_new!(2, 3)`. It is defined under a hidden name and re-exported as the type name, which only imports the
+/// macro namespace and leaves the type alone.
+fn ctor_macro(
+    name: &Ident,
+    ty: &str,
+    vis: &TokenStream,
+    lints: Option<&TokenStream>,
+) -> TokenStream {
+    let ty = format_ident!("{}", ty);
+    let macro_name = format_ident!("__overfn_ctor_{}", ty);
+    let (macro_lints, use_lints) = lint_attrs(lints);
+    quote! {
+        #macro_lints
+        macro_rules! #macro_name {
+            ($($args:tt)*) => (#name!($($args)*));
+        }
+        #use_lints
+        #vis use #macro_name as #ty;
+    }
+}
+
+/// Lint attributes of a generated macro and of its re-export: those given with `macros!(lints(..))` on both, or by
+/// default `allow(unused_macros)` and `allow(unused_imports)`, since a module rarely calls every set.
+fn lint_attrs(lints: Option<&TokenStream>) -> (TokenStream, TokenStream) {
+    match lints {
+        Some(lints) => (lints.clone(), lints.clone()),
+        None => (
+            quote! { #[allow(unused_macros)] },
+            quote! { #[allow(unused_imports)] },
+        ),
+    }
+}
+
+/// Options calling the overloads of a set, with the turbofish options of associated functions first: a `::<..>` first
+/// argument would otherwise fail to parse as an `expr` capture instead of moving on to the next option. The `partial`
+/// options, whose `_` placeholders the plain options would capture, go between them.
+fn turbofish_first<'a>(
+    functions: impl Iterator<Item = &'a FunctionData> + Clone,
+    krate: &TokenStream,
+    partial: Vec<TokenStream>,
+) -> Vec<TokenStream> {
+    let turbofish = functions
+        .clone()
+        .filter_map(|data| overload_option(data, krate, true));
+    let plain = functions.filter_map(|data| overload_option(data, krate, false));
+    turbofish.chain(partial).chain(plain).collect()
+}
+
+/// Option of a set macro calling one overload. `krate` prefixes the paths of free and associated functions, `$crate::`
+/// in exported macros. With `turbofish`, the option takes the generic arguments of the type of an associated function
+/// first (`Wrapper_new!(::<u32>, 5)` calls `Wrapper::<u32>::new_1(5)`), and other overloads have no such option.
+/// Overloads marked with `op = Trait` are called through the operator trait, which picks the overload of the arity
+/// from the types of the arguments.
+fn overload_option(
+    data: &FunctionData,
+    krate: &TokenStream,
+    turbofish: bool,
+) -> Option<TokenStream> {
+    let func = match &data.op {
+        Some(op) => format_ident!("{}", op.to_lowercase()),
+        None => format_ident!("{}", data.name),
+    };
+    let local = krate.is_empty();
+    let root = krate;
+    let krate = &item_prefix(data, krate);
+    let mut func_args = metavariables(data)
+        .into_iter()
+        .map(|arg| quote! { $ #arg })
+        .collect::<Vec<_>>();
+
+    let input_args = func_args
+        .iter()
+        .map(|arg| quote! { #arg: expr })
+        .collect::<Vec<_>>();
+
+    let skip = usize::from(data.receiver.is_some());
+    for &place in &data.places {
+        let arg = &func_args[skip + place];
+        func_args[skip + place] = quote! { &mut #arg };
+    }
+    match data.convert {
+        Convert::None => {}
+        Convert::Into => {
+            for arg in &mut func_args[skip..] {
+                *arg = quote! { ::core::convert::Into::into(#arg) };
+            }
+        }
+        Convert::Pack => {
+            let packed = func_args.split_off(skip);
+            func_args.push(quote! { ::core::convert::Into::into((#(#packed,)*)) });
+        }
+    }
+
+    // Generic arguments of the type as given in the attribute, or placeholders for the parameters of a generic impl
+    // given with `generic(..)`, left to inference.
+    let type_args = match &data.type_args {
+        Some(type_args) => Some(
+            type_args
+                .parse::<TokenStream>()
+                .expect("generic arguments are recorded from tokens"),
+        ),
+        None => (data.impl_params > 0).then(|| {
+            let params = (0..data.impl_params).map(|_| quote! { _ });
+            quote! { <#(#params),*> }
+        }),
+    };
+    let pre_args = match &data.arg_type {
+        // The arguments of the type are already given, with nothing left for a turbofish.
+        ArgType::Struct(_) if turbofish && data.type_args.is_some() => return None,
+        ArgType::Struct(name) if turbofish => {
+            let name = format_ident!("{}", name);
+            quote! { #krate #name::<$($generic),+>:: }
+        }
+        _ if turbofish => return None,
+        ArgType::Struct(name) => {
+            let name = format_ident!("{}", name);
+            let type_args = type_args.map(|args| quote! { ::#args });
+            quote! { #krate #name #type_args:: }
+        }
+        ArgType::Instance(_) if data.method_call => {
+            let self_arg = func_args.remove(0);
+            let self_arg = copied(self_arg, data.copy_receiver);
+            quote! { (#self_arg). }
+        }
+        ArgType::Instance(ty) => {
+            qualified_receiver(&mut func_args, data, local);
+            let ty = format_ident!("{}", ty);
+            let type_args = type_args.map(|args| quote! { ::#args });
+            quote! { #krate #ty #type_args:: }
+        }
+        ArgType::Trait(ty, trait_path) => {
+            qualified_receiver(&mut func_args, data, local);
+            let ty = format_ident!("{}", ty);
+            let trait_path = trait_path
+                .parse::<TokenStream>()
+                .expect("trait paths are recorded from tokens");
+            quote! { <#krate #ty #type_args as #trait_path>:: }
+        }
+        ArgType::Other => match &data.op {
+            Some(op) => {
+                let op = format_ident!("{}", op);
+                quote! { ::core::ops::#op:: }
+            }
+            None => quote! { #krate },
+        },
+    };
+
+    let pattern = match turbofish {
+        true => quote! { ::<$($generic:ty),+> #(, #input_args)* $(,)? },
+        false if input_args.is_empty() => quote! {},
+        false => quote! { #(#input_args),* $(,)? },
+    };
+    let call = quote! { #pre_args #func(#(#func_args),*) };
+    let call = match data.safety {
+        Safety::Wrapped => quote! { unsafe { #call } },
+        Safety::Safe | Safety::Unsafe => call,
+    };
+    let call = match &data.unify_return {
+        Some(unify) => {
+            let unify = format_ident!("{}", unify);
+            let variant = arity_variant(data.n_args);
+            quote! { #root #unify::#variant(#call) }
+        }
+        None => call,
+    };
+    Some(quote! {
+        (#pattern) => (
+            #call
+        )
+    })
+}
+
+/// Names of the metavariables capturing the arguments of an overload: `receiver` and the names of the parameters as
+/// written, so errors and IDE hints show them. Patterns, names used twice or by the other metavariables, and the
+/// packing options of `into`, whose arguments don't match the parameters, fall back to `arg_N`.
+fn metavariables(data: &FunctionData) -> Vec<Ident> {
+    let names = data
+        .receiver
+        .map(|_| "receiver".to_owned())
+        .into_iter()
+        .chain(
+            data.arg_names
+                .iter()
+                .map(|name| name.trim_start_matches("r#").to_owned()),
+        )
+        .collect::<Vec<_>>();
+    let named = data.convert != Convert::Pack && names.len() == data.n_args;
+    (0..data.n_args)
+        .map(|i| match named {
+            true => {
+                let name = &names[i];
+                let unique = names.iter().filter(|other| *other == name).count() == 1;
+                match unique && name != "generic" && name != "_" && !name.starts_with("arg_") {
+                    true => format_ident!("{}", name),
+                    false => format_ident!("arg_{}", i),
+                }
+            }
+            false => format_ident!("arg_{}", i),
+        })
+        .collect()
+}
+
+/// Last options of a set macro, failing calls with a number of arguments no overload takes with the arities that the
+/// set accepts instead of the generic "no rules expected" error. Calls with a missing arity up to two past the
+/// highest one also get the closest overload, the lowest of two as close, with the names of its parameters.
+fn arity_error<'a>(
+    name: &Ident,
+    functions: impl Iterator<Item = &'a FunctionData> + Clone,
+) -> TokenStream {
+    let members = functions.collect::<Vec<_>>();
+    let mut arities = members
+        .iter()
+        .map(|data| data.n_args.to_string())
+        .collect::<Vec<_>>();
+    arities.dedup();
+    let arities = match arities.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => return quote! {},
+    };
+    let receiver = match members.iter().any(|data| data.receiver.is_some()) {
+        true => ", counting the receiver",
+        false => "",
+    };
+    let highest = members.iter().map(|data| data.n_args).max().unwrap_or(0);
+    let hints = (0..=highest + 2)
+        .filter(|&n_args| members.iter().all(|data| data.n_args != n_args))
+        .map(|n_args| {
+            let closest = members
+                .iter()
+                .min_by_key(|data| data.n_args.abs_diff(n_args))
+                .expect("sets with an arity error have overloads");
+            let params = metavariables(closest)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            let message = format!(
+                "no overload of `{}!` takes {} argument(s){}: it takes {} argument(s), did you mean `{}!({})`?",
+                name,
+                n_args,
+                receiver,
+                arities,
+                name,
+                params.join(", ")
+            );
+            let args = (0..n_args).map(|i| format_ident!("arg_{}", i));
+            let pattern = match n_args {
+                0 => quote! {},
+                _ => quote! { #($#args:expr),* $(,)? },
+            };
+            quote! { (#pattern) => (::core::compile_error!(#message)); }
+        });
+    let message = format!(
+        "no overload of `{}!` takes these arguments: it takes {} argument(s){}",
+        name, arities, receiver
+    );
+    quote! {
+        #(#hints)*
+        ($($args:tt)*) => (::core::compile_error!(#message))
+    }
+}
+
+/// Options of the arities with `by_len` overloads, passing the arguments as a tuple to the spread trait of the set so
+/// the impl for the array length of the argument is picked, and a warning for the `by_len` overloads the trait can't
+/// be implemented for.
+fn by_len_options(name: &Ident, functions: &[FunctionData]) -> (Vec<TokenStream>, TokenStream) {
+    let spread = format_ident!("__overfn_spread_{}", name);
+    let mut arities = functions
+        .iter()
+        .filter(|data| data.array_len.is_some())
+        .map(|data| data.n_args)
+        .collect::<Vec<_>>();
+    arities.dedup();
+    let options = arities
+        .into_iter()
+        .map(|n_args| {
+            let args = (0..n_args)
+                .map(|i| format_ident!("arg_{}", i))
+                .collect::<Vec<_>>();
+            quote! {
+                (#($#args: expr),* $(,)?) => (#spread::__overfn_spread((#($#args,)*)))
+            }
+        })
+        .collect();
+    let unnameable = functions
+        .iter()
+        .filter(|data| data.array_len.is_some() && spread_signature(data).is_none())
+        .map(|data| {
+            warning(&format!(
+                "`{}` can't be called through `{}!`: `by_len` overloads need a signature that can be named outside \
+                 of the function",
+                data.name, name
+            ))
+        })
+        .collect();
+    (options, unnameable)
+}
+
+/// Option of a set with overloads marked with `keywords`, taking the arguments by parameter name. It goes first so
+/// `name = value` arguments aren't taken for assignments by the `expr` options.
+fn keyword_options(name: &Ident, functions: &[FunctionData]) -> Vec<TokenStream> {
+    let candidates = functions
+        .iter()
+        .filter_map(|data| {
+            let defaults = data.keywords.as_ref()?;
+            let path = call_path(data)?;
+            let params = data.arg_names.iter().map(|param| {
+                let default =
+                    defaults
+                        .iter()
+                        .find(|(name, _)| name == param)
+                        .map(|(_, default)| {
+                            let default = default
+                                .parse::<TokenStream>()
+                                .expect("defaults are recorded from tokens");
+                            quote! { = #default }
+                        });
+                let param = param
+                    .parse::<TokenStream>()
+                    .expect("parameter names are recorded from tokens");
+                quote! { #param #default }
+            });
+            let wrapped = (data.safety == Safety::Wrapped).then(|| quote! { unsafe });
+            Some(quote! { #wrapped { #path } [#(#params),*] })
+        })
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+    vec![quote! {
+        ($($key:ident = $value:expr),+ $(,)?) => (
+            ::overfn::__overfn_keywords!(#name; #(#candidates)* ; $($key = $value),+)
+        )
+    }]
+}
+
+/// Call of the first candidate of a keyword option taking a parameter of every name given, with a default for each
+/// parameter left out. The arguments are evaluated in the order they are written.
+pub(crate) fn keyword_call(call: &KeywordCall) -> TokenStream {
+    let keys = call
+        .args
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+    if let Some((i, key)) = keys
+        .iter()
+        .enumerate()
+        .find(|(i, key)| keys[..*i].contains(key))
+    {
+        return syn::Error::new(call.args[i].0.span(), format!("`{}` is given twice", key))
+            .into_compile_error();
+    }
+    let takes = |candidate: &KeywordCandidate| {
+        let params = candidate
+            .params
+            .iter()
+            .map(|(param, default)| (param.to_string(), default.is_some()))
+            .collect::<Vec<_>>();
+        keys.iter()
+            .all(|key| params.iter().any(|(param, _)| param == key))
+            && params
+                .iter()
+                .all(|(param, default)| *default || keys.contains(param))
+    };
+    let Some(candidate) = call.candidates.iter().find(|candidate| takes(candidate)) else {
+        let signatures = call
+            .candidates
+            .iter()
+            .map(|candidate| {
+                let params = candidate
+                    .params
+                    .iter()
+                    .map(|(param, default)| match default {
+                        Some(_) => format!("{} = ..", param),
+                        None => param.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("`({})`", params.join(", "))
+            })
+            .collect::<Vec<_>>();
+        let span = call
+            .args
+            .first()
+            .map_or_else(Span::call_site, |(key, _)| key.span());
+        return syn::Error::new(
+            span,
+            format!(
+                "no overload of `{}!` takes the arguments {} by name: it takes {}",
+                call.name,
+                keys.iter()
+                    .map(|key| format!("`{}`", key))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                signatures.join(" or ")
+            ),
+        )
+        .into_compile_error();
+    };
+    let bindings = (0..keys.len())
+        .map(|i| format_ident!("__overfn_arg_{}", i))
+        .collect::<Vec<_>>();
+    let values = call.args.iter().map(|(_, value)| value);
+    let args = candidate.params.iter().map(|(param, default)| {
+        let param = param.to_string();
+        match keys.iter().position(|key| *key == param) {
+            Some(i) => bindings[i].to_token_stream(),
+            None => default.to_token_stream(),
+        }
+    });
+    let path = &candidate.path;
+    let call = quote! { #path(#(#args),*) };
+    let call = match candidate.wrapped {
+        true => quote! { unsafe { #call } },
+        false => call,
+    };
+    quote! {
+        match (#(#values,)*) {
+            (#(#bindings,)*) => #call,
+        }
+    }
+}
+
+/// Options of the arities with `by_literal` overloads taking only literals, which
+/// [`__overfn_literal!`](macro@crate::__overfn_literal) routes to the overload whose parameter types fit them. They go
+/// first so these calls don't fall into the `expr` option of the arity, which calls its first overload.
+fn literal_options(functions: &[FunctionData]) -> Vec<TokenStream> {
+    let mut arities = functions
+        .iter()
+        .filter(|data| data.by_literal)
+        .map(|data| data.n_args)
+        .collect::<Vec<_>>();
+    arities.dedup();
+    arities
+        .into_iter()
+        .map(|n_args| {
+            let candidates = functions
+                .iter()
+                .filter(|data| data.by_literal && data.n_args == n_args)
+                .filter_map(|data| {
+                    let path = call_path(data)?;
+                    let types = data.arg_types.iter().map(|ty| {
+                        ty.parse::<TokenStream>()
+                            .expect("argument types are recorded from tokens")
+                    });
+                    Some(quote! { { #path } [#(#types),*] })
+                });
+            let args = (0..n_args)
+                .map(|i| format_ident!("arg_{}", i))
+                .collect::<Vec<_>>();
+            quote! {
+                (#($#args: literal),* $(,)?) => (::overfn::__overfn_literal!(#(#candidates)* ; #($#args),*))
+            }
+        })
+        .collect()
+}
+
+/// Call of the first candidate of a literal option whose parameter types fit the literals, or of the first candidate
+/// when none does, as the `expr` option would.
+pub(crate) fn literal_call(call: &LiteralCall) -> TokenStream {
+    let args = call.args.iter().collect::<Vec<_>>();
+    let fits = |types: &[Type]| {
+        types.len() == args.len()
+            && types.iter().zip(&args).all(|(ty, arg)| {
+                literal(arg).is_some_and(|(negative, lit)| fits(lit, negative, ty))
+            })
+    };
+    let (path, _) = call
+        .candidates
+        .iter()
+        .find(|(_, types)| fits(types))
+        .or(call.candidates.first())
+        .expect("literal options have at least one candidate");
+    quote! { #path(#(#args),*) }
+}
+
+/// Literal of an argument and whether it is negated.
+fn literal(arg: &Expr) -> Option<(bool, &Lit)> {
+    match arg {
+        Expr::Lit(lit) => Some((false, &lit.lit)),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+            literal(&unary.expr).map(|(_, lit)| (true, lit))
+        }
+        Expr::Group(group) => literal(&group.expr),
+        _ => None,
+    }
+}
+
+/// Whether a literal has the type `ty`: unsuffixed integers fit every integer type (signed ones when negated) and
+/// unsuffixed floats both float types.
+fn fits(lit: &Lit, negative: bool, ty: &Type) -> bool {
+    const INTEGERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    let is = |ty: &Type, name: &str| matches!(ty, Type::Path(path) if path.path.is_ident(name));
+    match ty {
+        Type::Group(group) => fits(lit, negative, &group.elem),
+        Type::Paren(paren) => fits(lit, negative, &paren.elem),
+        Type::Reference(reference) => match (lit, &*reference.elem) {
+            (Lit::Str(_), elem) => is(elem, "str"),
+            (Lit::ByteStr(_), Type::Slice(slice)) => is(&slice.elem, "u8"),
+            (Lit::ByteStr(_), Type::Array(array)) => is(&array.elem, "u8"),
+            _ => false,
+        },
+        Type::Path(path) => {
+            let Some(name) = path.path.get_ident().map(Ident::to_string) else {
+                return false;
+            };
+            match lit {
+                Lit::Int(int) if int.suffix().is_empty() => {
+                    INTEGERS.contains(&name.as_str()) && (!negative || name.starts_with('i'))
+                }
+                Lit::Int(int) => int.suffix() == name,
+                Lit::Float(float) if float.suffix().is_empty() => name == "f32" || name == "f64",
+                Lit::Float(float) => float.suffix() == name,
+                Lit::Char(_) => name == "char",
+                Lit::Byte(_) => name == "u8",
+                Lit::Bool(_) => name == "bool",
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Replaces the receiver among the arguments of a qualified call with the receiver borrowed as the method takes it.
+/// Local macros borrow it through [`__overfn_receiver!`](macro@crate::__overfn_receiver), which keeps nested calls
+/// apart, while exported macros can't rely on the caller depending on this crate and borrow it in place.
+fn qualified_receiver(func_args: &mut [TokenStream], data: &FunctionData, local: bool) {
+    if let Some(kind) = data.receiver {
+        let self_arg = copied(func_args[0].clone(), data.copy_receiver);
+        let reference = match kind {
+            ReceiverKind::Value => {
+                func_args[0] = self_arg;
+                return;
+            }
+            ReceiverKind::Ref => quote! { & },
+            ReceiverKind::Mut => quote! { &mut },
+        };
+        func_args[0] = match local {
+            true => quote! { ::overfn::__overfn_receiver!(#reference #self_arg) },
+            false => auto_ref(&self_arg, reference, &format_ident!("__overfn_receiver")),
+        };
+    }
+}
+
+/// Receivers borrowed by [`__overfn_receiver!`](macro@crate::__overfn_receiver) so far, numbering the helper trait of
+/// each one.
+static RECEIVERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Receiver borrowed by [`__overfn_receiver!`](macro@crate::__overfn_receiver). Every expansion names its helper
+/// method differently, since the receiver can contain another borrowed receiver whose helper trait is also in scope.
+pub(crate) fn borrowed_receiver(receiver: &BorrowedReceiver) -> TokenStream {
+    let reference = match receiver.mutable {
+        true => quote! { &mut },
+        false => quote! { & },
+    };
+    let id = RECEIVERS.fetch_add(1, Ordering::Relaxed);
+    let method = format_ident!("__overfn_receiver_{}", id);
+    auto_ref(&receiver.receiver.to_token_stream(), reference, &method)
+}
+
+/// Receiver borrowed for a qualified call the way the method call syntax borrows it: through a method taking `&self`
+/// or `&mut self`, so places are borrowed, references are reborrowed (even from bindings that aren't `mut`) and smart
+/// pointers are dereferenced, rather than prefixing the argument with `&mut`.
+fn auto_ref(receiver: &TokenStream, reference: TokenStream, method: &Ident) -> TokenStream {
+    quote! {{
+        trait __OverfnReceiver {
+            fn #method(#reference self) -> #reference Self {
+                self
+            }
+        }
+        impl<T: ?::core::marker::Sized> __OverfnReceiver for T {}
+        (#receiver).#method()
+    }}
+}
+
+/// Receiver of a method call, copied first with `receiver = copy`. Copying through a `Copy` bound makes receivers of
+/// other types fail to compile instead of being moved.
+fn copied(receiver: TokenStream, copy: bool) -> TokenStream {
+    match copy {
+        true => quote! {{
+            fn copy<T: ::core::marker::Copy>(value: &T) -> T {
+                *value
+            }
+            copy(&(#receiver))
+        }},
+        false => receiver,
+    }
+}
+
+/// `#[macro_export]` macro of a set with an overload marked `export`, calling the overloads that aren't `internal`
+/// through `$crate` and re-exported under the name of the set, or of any set with `export` from `macros!(vis = pub)`,
+/// next to the `macros!()` of their `module = path`. Warns about exported overloads that aren't `pub`, which other
+/// crates wouldn't be able to call.
+fn exported_macro(name: &Ident, functions: &[FunctionData], export: bool) -> Option<TokenStream> {
+    if !export && !functions.iter().any(|data| data.export) {
+        return None;
+    }
+    let members = functions
+        .iter()
+        .filter(|data| !data.internal && data.array_len.is_none())
+        .collect::<Vec<_>>();
+    let krate = quote! { $crate:: };
+    // The macro calls itself through its re-export, in the module of its overloads: the `#[macro_export]` name can't be
+    // used by absolute path from the crate defining it.
+    let module = members
+        .first()
+        .map_or_else(|| krate.clone(), |data| item_prefix(data, &krate));
+    let path = quote! { #module #name };
+    let partial = partial_options(&path, members.iter().copied(), &krate);
+    let options = turbofish_first(members.iter().copied(), &krate, partial);
+    let hidden = members
+        .iter()
+        .filter(|data| data.reach != Reach::Public)
+        .map(|data| {
+            warning(&format!(
+                "`{}` is exported through `{}!` but isn't `pub`; make it `pub` or mark it `#[overload(internal)]`",
+                data.name, name
+            ))
+        });
+
+    let export = format_ident!("__overfn_export_{}", name);
+    let instance = functions.iter().any(|data| data.receiver.is_some());
+    let mut spread = arity_options(members.iter().copied());
+    spread.extend(literal_spread_options(&path, instance));
+    spread.extend(splat_options(&path, members.iter().copied(), instance));
+    let arity_error = arity_error(name, members.iter().copied());
+    Some(quote! {
+        #(#hidden)*
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #export {
+            #(#spread;)*
+            #(#options;)*
+            #arity_error
+        }
+        #[doc(inline)]
+        pub use #export as #name;
+    })
+}
+
+/// Macros of the standard library prelude, which a generated `macro_rules!` of the same name would shadow for the rest
+/// of the module.
+const STD_MACROS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "cfg",
+    "column",
+    "compile_error",
+    "concat",
+    "dbg",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "env",
+    "eprint",
+    "eprintln",
+    "file",
+    "format",
+    "format_args",
+    "include",
+    "include_bytes",
+    "include_str",
+    "line",
+    "matches",
+    "module_path",
+    "option_env",
+    "panic",
+    "print",
+    "println",
+    "stringify",
+    "thread_local",
+    "todo",
+    "unimplemented",
+    "unreachable",
+    "vec",
+    "write",
+    "writeln",
+];
+
+/// Error for a set whose macro would shadow a macro of the standard library, pointing at the `macros!()` generating it
+/// since the overloads are expanded by then.
+pub(crate) fn shadowing_error(name: &str, functions: &[FunctionData]) -> Option<TokenStream> {
+    if !STD_MACROS.contains(&name) {
+        return None;
+    }
+    let locations = functions
+        .iter()
+        .map(|data| data.location.to_string())
+        .collect::<Vec<_>>();
+    let message = format!(
+        "`{}!` would shadow the standard macro for the rest of the module (overloads at {}); name the macro with \
+         `#[overload_set(\"..\")]` or prefix the generated macros with `macros!(prefix = \"ov_\")`",
+        name,
+        locations.join(", ")
+    );
+    Some(syn::Error::new(Span::call_site(), message).into_compile_error())
+}
+
+/// Error for a set whose overloads declared in different modules take the same arguments, which the macro couldn't
+/// tell apart, suggesting to generate the overloads of each module apart.
+pub(crate) fn module_clash(name: &str, functions: &[FunctionData]) -> Option<TokenStream> {
+    let (first, other) = functions.iter().enumerate().find_map(|(i, data)| {
+        let other = functions[i + 1..].iter().find(|other| {
+            other.n_args == data.n_args
+                && other.priority == data.priority
+                && other.module != data.module
+        })?;
+        Some((data, other))
+    })?;
+    let module = |data: &FunctionData| match &data.module {
+        Some(module) => format!("in `{}`", tidy(module)),
+        None => String::from("without `module`"),
+    };
+    let message = format!(
+        "`{}!` has overloads taking {} argument(s) declared {} (at {}) and {} (at {}); generate the \
+         overloads of each module apart with `macros!(module = path)`",
+        name,
+        first.n_args,
+        module(first),
+        first.location,
+        module(other),
+        other.location
+    );
+    Some(syn::Error::new(Span::call_site(), message).into_compile_error())
+}
+
+/// Compile-time warning. Stable proc macros can only emit errors, so the message is attached to a deprecated item
+/// that is immediately used.
+pub(crate) fn warning(message: &str) -> TokenStream {
+    let note = format!("overfn: {}", message);
+    quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct OverfnWarning;
+            #[allow(dead_code)]
+            fn warn() {
+                let _ = OverfnWarning;
+            }
+        };
+    }
+}
+
+/// Placeholder for the name of the crate in the sets saved by `macros!(persist)`, replaced by the name given to
+/// `macros!(import = "..")`.
+const IMPORTED_CRATE: &str = "__overfn_import_crate";
+
+/// Sets generated by `macros!(persist)` as saved for `macros!(import = "..")`: the name of each set followed by the
+/// options of its macro in braces. As in exported macros, only the `pub` overloads that aren't `internal` are called,
+/// through the crate root.
+pub(crate) fn persisted_sets(sets: &[(String, Vec<FunctionData>)]) -> String {
+    let krate = format_ident!("{}", IMPORTED_CRATE);
+    let krate = quote! { ::#krate:: };
+    sets.iter()
+        .filter_map(|(name, functions)| {
+            let functions = unified(functions);
+            let members = functions
+                .iter()
+                .filter(|data| {
+                    !data.internal && data.array_len.is_none() && data.reach == Reach::Public
+                })
+                .collect::<Vec<_>>();
+            if members.is_empty() {
+                return None;
+            }
+            let name = set_ident(name);
+            let instance = members.iter().any(|data| data.receiver.is_some());
+            let mut options = arity_options(members.iter().copied());
+            options.extend(literal_spread_options(&name, instance));
+            options.extend(splat_options(&name, members.iter().copied(), instance));
+            let partial = partial_options(&name, members.iter().copied(), &krate);
+            options.extend(turbofish_first(members.iter().copied(), &krate, partial));
+            let arity_error = arity_error(&name, members.iter().copied());
+            Some(quote! { #name { #(#options;)* #arity_error } }.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Macros of the sets saved by `macros!(persist)` in `krate`, prefixed with `prefix`, for the sets `selects` accepts.
+pub(crate) fn imported_macros(
+    persisted: TokenStream,
+    krate: &Ident,
+    prefix: &str,
+    lints: Option<&TokenStream>,
+    selects: impl Fn(&str) -> bool,
+) -> TokenStream {
+    let (macro_lints, use_lints) = lint_attrs(lints);
+    let mut tokens = persisted.into_iter();
+    let mut macros = TokenStream::new();
+    while let (Some(TokenTree::Ident(name)), Some(TokenTree::Group(options))) =
+        (tokens.next(), tokens.next())
+    {
+        // Saved names keep the `r#` of keywords, which the prefix would land in the middle of.
+        let name = name.unraw();
+        if !selects(&name.to_string()) {
+            continue;
+        }
+        let prefixed = set_ident(&format!("{}{}", prefix, name));
+        let name = set_ident(&name.to_string());
+        let options = imported_options(options.stream(), krate, &name, &prefixed);
+        macros.extend(quote! {
+            #macro_lints
+            macro_rules! #prefixed {
+                #options
+            }
+            #use_lints
+            pub(crate) use #prefixed;
+        });
+    }
+    macros
+}
+
+/// Options of a saved set with the placeholder of the crate replaced by `krate` and the recursive calls of the macro
+/// `name` renamed to `prefixed`.
+fn imported_options(
+    options: TokenStream,
+    krate: &Ident,
+    name: &Ident,
+    prefixed: &Ident,
+) -> TokenStream {
+    let tokens = options.into_iter().collect::<Vec<_>>();
+    let bang = |i: usize| matches!(tokens.get(i + 1), Some(TokenTree::Punct(punct)) if punct.as_char() == '!');
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| match token {
+            TokenTree::Ident(ident) if ident == IMPORTED_CRATE => TokenTree::Ident(krate.clone()),
+            TokenTree::Ident(ident) if ident == name && bang(i) => {
+                TokenTree::Ident(prefixed.clone())
+            }
+            TokenTree::Group(group) => {
+                let stream = imported_options(group.stream(), krate, name, prefixed);
+                TokenTree::Group(Group::new(group.delimiter(), stream))
+            }
+            token => token.clone(),
+        })
+        .collect()
+}
+
+/// Macro of a set collected by [`define_overloads!`](macro@crate::define_overloads), with one option per arity up to
+/// [`MAX_SET_ARITY`].
+pub(crate) fn set_macro(set: &SetPath) -> TokenStream {
+    let name = match set {
+        SetPath::Free(set) => set.clone(),
+        SetPath::Static(ty, ident) | SetPath::Instance(ty, ident, _) => {
+            format_ident!("{}_{}", ty, ident)
+        }
+    };
+    let options = (0..=MAX_SET_ARITY)
+        .filter_map(|n_args| {
+            let mut func_args = (0..n_args)
+                .map(|i| format_ident!("arg_{}", i))
+                .map(|arg| quote! { $ #arg })
+                .collect::<Vec<_>>();
+
+            let input_args = func_args
+                .iter()
+                .map(|arg| quote! { #arg: expr })
+                .collect::<Vec<_>>();
+
+            let call = match set {
+                SetPath::Free(set) => {
+                    let helper = format_ident!("__overfn_{}_{}", set, n_args);
+                    quote! { #helper!(#(#func_args),*) }
+                }
+                SetPath::Static(ty, ident) => {
+                    let func = format_ident!("{}_{}", ident, n_args);
+                    quote! { #ty::#func(#(#func_args),*) }
+                }
+                SetPath::Instance(..) if n_args == 0 => return None,
+                SetPath::Instance(_, ident, skip_self) => {
+                    let arity = n_args - usize::from(*skip_self);
+                    let func = format_ident!("{}_{}", ident, arity);
+                    let self_arg = func_args.remove(0);
+                    quote! { (#self_arg).#func(#(#func_args),*) }
+                }
+            };
+
+            let trailing = (n_args > 0).then(|| quote! { $(,)? });
+            Some(quote! {
+                (#(#input_args),* #trailing) => (
+                    #call
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    let turbofish = match set {
+        SetPath::Static(ty, ident) => (0..=MAX_SET_ARITY)
+            .map(|n_args| {
+                let func = format_ident!("{}_{}", ident, n_args);
+                let args = (0..n_args)
+                    .map(|i| format_ident!("arg_{}", i))
+                    .collect::<Vec<_>>();
+                quote! {
+                    (::<$($generic:ty),+> #(, $#args:expr)* $(,)?) => (
+                        #ty::<$($generic),+>::#func(#($#args),*)
+                    )
+                }
+            })
+            .collect(),
+        SetPath::Free(_) | SetPath::Instance(..) => Vec::new(),
+    };
+    let spread = literal_spread_options(&name, matches!(set, SetPath::Instance(..)));
+
+    quote! {
+        macro_rules! #name {
+            #(#spread;)*
+            #(#turbofish;)*
+            #(#options);*
+        }
+        #[allow(unused_imports)]
+        pub(crate) use #name;
+    }
+}
+
+/// Macro invocation written as a plain call by [`call!`](macro@crate::call): `Type::name(args)` calls `Type_name!`,
+/// forwarding the generic arguments of the type as a turbofish, and any other path calls the macro named after its
+/// last segment, so `add(args)` calls `add!` and `module::add(args)` calls `module::add!`.
+pub(crate) fn call_macro(call: &Expr) -> syn::Result<TokenStream> {
+    let (func, args) = match call {
+        Expr::Call(call) => (&*call.func, &call.args),
+        Expr::MethodCall(call) => {
+            return Err(syn::Error::new(
+                call.method.span(),
+                "`call!` can't know the type of the receiver; write the method as `Type::method(receiver, ..)`",
+            ))
+        }
+        _ => return Err(syn::Error::new(call.span(), "expected a function call")),
+    };
+    let path = match func {
+        Expr::Path(path) if path.qself.is_none() => &path.path,
+        _ => {
+            return Err(syn::Error::new(
+                func.span(),
+                "expected the path of an overloaded function",
+            ))
+        }
+    };
+
+    let segments = path.segments.iter().collect::<Vec<_>>();
+    let (last, rest) = segments.split_last().unwrap();
+    if !last.arguments.is_none() {
+        return Err(syn::Error::new(
+            last.arguments.span(),
+            "overloaded functions take no generic arguments; pass them to the type instead",
+        ));
+    }
+    let owner = rest
+        .last()
+        .filter(|segment| segment.ident.to_string().starts_with(char::is_uppercase));
+    let (name, prefix, turbofish) = match owner {
+        Some(owner) => {
+            let name = format_ident!("{}_{}", owner.ident, last.ident, span = last.ident.span());
+            let turbofish = match &owner.arguments {
+                PathArguments::None => None,
+                PathArguments::AngleBracketed(generics) => {
+                    let generics = &generics.args;
+                    Some(quote! { ::<#generics> })
+                }
+                PathArguments::Parenthesized(arguments) => {
+                    return Err(syn::Error::new(arguments.span(), "unexpected arguments"))
+                }
+            };
+            (name, &rest[..rest.len() - 1], turbofish)
+        }
+        None => (last.ident.clone(), rest, None),
+    };
+
+    if let Some(segment) = prefix.iter().find(|segment| !segment.arguments.is_none()) {
+        return Err(syn::Error::new(
+            segment.arguments.span(),
+            "unexpected generic arguments",
+        ));
+    }
+    let leading = &path.leading_colon;
+    let prefix = prefix.iter().map(|segment| &segment.ident);
+    let separator = (turbofish.is_some() && !args.is_empty()).then(|| quote! { , });
+    Ok(quote! { #leading #(#prefix::)* #name!(#turbofish #separator #args) })
+}
+
+/// Test module generated by [`test_suite!`](macro@crate::test_suite), with a test per case calling the set macro and
+/// comparing its result with the expected value.
+pub(crate) fn test_suite(suite: &TestSuite) -> TokenStream {
+    let name = &suite.name;
+    let module = format_ident!("{}_suite", name);
+    let cases = suite.cases.iter().enumerate().map(|(i, (args, expected))| {
+        let case = format_ident!("case_{}", i);
+        quote! {
+            #[test]
+            fn #case() {
+                ::core::assert_eq!(#name!(#args), #expected);
+            }
+        }
+    });
+    quote! {
+        #[cfg(test)]
+        #[allow(non_snake_case)]
+        mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #(#cases)*
+        }
+    }
+}
+
+/// Options spreading a literal array or tuple (`add!(..[1, 2])`, `add!(..(1, 2))`) into positional arguments, which
+/// selects the overload by the number of elements. Instance methods spread the arguments after the receiver. They go
+/// before the regular options because `..[1, 2]` is also a valid range expression.
+fn literal_spread_options(name: &impl ToTokens, instance: bool) -> Vec<TokenStream> {
+    let receiver = match instance {
+        true => quote! { $receiver:expr, },
+        false => quote! {},
+    };
+    let forwarded = match instance {
+        true => quote! { $receiver, },
+        false => quote! {},
+    };
+    vec![
+        quote! { (#receiver .. [$($arg:expr),* $(,)?]) => (#name!(#forwarded $($arg),*)) },
+        quote! { (#receiver .. ($($arg:expr),* $(,)?)) => (#name!(#forwarded $($arg),*)) },
+    ]
+}
+
+/// Options answering which numbers of arguments the set takes, counting the receiver of methods like the macro:
+/// `add!(@arity 2)` expands to `true` or `false` and `add!(@arities)` to an array of the arities, `[1, 2]`.
+fn arity_options<'a>(functions: impl Iterator<Item = &'a FunctionData>) -> Vec<TokenStream> {
+    let mut arities = functions.map(|data| data.n_args).collect::<Vec<_>>();
+    arities.sort_unstable();
+    arities.dedup();
+    let arities = arities
+        .into_iter()
+        .map(proc_macro2::Literal::usize_unsuffixed)
+        .collect::<Vec<_>>();
+    let mut options = arities
+        .iter()
+        .map(|arity| quote! { (@arity #arity) => (true) })
+        .collect::<Vec<_>>();
+    options.push(quote! { (@arity $arity:literal) => (false) });
+    options.push(quote! { (@arities) => ([#(#arities),*]) });
+    options
+}
+
+/// Options splatting a tuple expression of a given length into positional arguments, `add!(@splat(2) pair)` calling
+/// `add!(pair.0, pair.1)`, for every arity of the set. Unlike spreading, the length is written at the call site, so
+/// it works for every overload. Instance methods splat the arguments after the receiver.
+fn splat_options<'a>(
+    name: &impl ToTokens,
+    functions: impl Iterator<Item = &'a FunctionData>,
+    instance: bool,
+) -> Vec<TokenStream> {
+    let (receiver, forwarded) = match instance {
+        true => (quote! { $receiver:expr, }, quote! { $receiver, }),
+        false => (quote! {}, quote! {}),
+    };
+    let mut lengths = functions
+        .map(|data| data.n_args - usize::from(instance && data.receiver.is_some()))
+        .filter(|&len| len > 0)
+        .collect::<Vec<_>>();
+    lengths.sort_unstable();
+    lengths.dedup();
+    lengths
+        .into_iter()
+        .map(|len| {
+            let fields = (0..len).map(syn::Index::from);
+            let len = proc_macro2::Literal::usize_unsuffixed(len);
+            quote! {
+                (#receiver @splat(#len) $tuple:expr) => ({
+                    let tuple = $tuple;
+                    #name!(#forwarded #(tuple.#fields),*)
+                })
+            }
+        })
+        .collect()
+}
+
+/// Highest arity whose calls can leave any of their arguments to a closure with `_`, since every combination of
+/// placeholders needs an option of its own.
+const MAX_PARTIAL_ARITY: usize = 4;
+
+/// Options turning a call with `_` placeholders into a closure taking the missing arguments in order, `add!(2, _)`
+/// becoming `|arg_1| add!(2, arg_1)`. They go before the regular options, where `_` would be captured as an `expr`.
+/// A missing receiver is annotated with the type of the method, which the closure can't infer from a method call;
+/// `krate` prefixes the type as in [`overload_option`].
+fn partial_options<'a>(
+    name: &impl ToTokens,
+    functions: impl Iterator<Item = &'a FunctionData>,
+    krate: &TokenStream,
+) -> Vec<TokenStream> {
+    let mut firsts = functions
+        .filter(|data| (1..=MAX_PARTIAL_ARITY).contains(&data.n_args))
+        .collect::<Vec<_>>();
+    firsts.dedup_by_key(|data| data.n_args);
+    firsts
+        .into_iter()
+        .flat_map(|data| (1..1usize << data.n_args).map(move |mask| (data, mask)))
+        .map(|(data, mask)| {
+            let receiver = match (&data.arg_type, data.receiver) {
+                (ArgType::Instance(ty) | ArgType::Trait(ty, _), Some(kind))
+                    if !data.generic && !data.copy_receiver =>
+                {
+                    let krate = item_prefix(data, krate);
+                    let ty = format_ident!("{}", ty);
+                    Some(match kind {
+                        ReceiverKind::Value => quote! { : #krate #ty },
+                        ReceiverKind::Ref => quote! { : &#krate #ty },
+                        ReceiverKind::Mut => quote! { : &mut #krate #ty },
+                    })
+                }
+                _ => None,
+            };
+            let args = (0..data.n_args)
+                .map(|i| format_ident!("arg_{}", i))
+                .collect::<Vec<_>>();
+            let missing = args
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(i, arg)| match i {
+                    0 => quote! { #arg #receiver },
+                    _ => quote! { #arg },
+                });
+            let pattern = args
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| match mask & (1 << i) {
+                    0 => quote! { $#arg:expr },
+                    _ => quote! { _ },
+                });
+            let forwarded = args
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| match mask & (1 << i) {
+                    0 => quote! { $#arg },
+                    _ => quote! { #arg },
+                });
+            quote! {
+                (#(#pattern),* $(,)?) => (|#(#missing),*| #name!(#(#forwarded),*))
+            }
+        })
+        .collect()
+}
+
+/// Option spreading any tuple or array expression (`add!(..pair)`) through a trait implemented for the argument
+/// tuple of every overload, and for arrays when all its arguments share a type. Only overloads whose signature is
+/// fully concrete get an implementation; the rest can still be spread from literals.
+fn typed_spread(name: &Ident, functions: &[FunctionData]) -> (TokenStream, TokenStream) {
+    let spread = format_ident!("__overfn_spread_{}", name);
+    let impls = functions
+        .iter()
+        .filter_map(|data| {
+            let (types, output) = spread_signature(data)?;
+            let path = call_path(data)?;
+            let fields = (0..types.len()).map(syn::Index::from);
+            let tuple = quote! {
+                #[allow(deprecated)]
+                impl #spread for (#(#types,)*) {
+                    type Output = #output;
+                    fn __overfn_spread(self) -> Self::Output {
+                        #path(#(self.#fields),*)
+                    }
+                }
+            };
+
+            let same_type = types
+                .first()
+                .filter(|_| data.arg_types.iter().all(|ty| *ty == data.arg_types[0]));
+            let array = same_type.map(|ty| {
+                let len = types.len();
+                let names = (0..len)
+                    .map(|i| format_ident!("arg_{}", i))
+                    .collect::<Vec<_>>();
+                quote! {
+                    #[allow(deprecated)]
+                    impl #spread for [#ty; #len] {
+                        type Output = #output;
+                        fn __overfn_spread(self) -> Self::Output {
+                            let [#(#names),*] = self;
+                            #path(#(#names),*)
+                        }
+                    }
+                }
+            });
+            Some(quote! { #tuple #array })
+        })
+        .collect::<Vec<_>>();
+
+    if impls.is_empty() {
+        return (quote! {}, quote! {});
+    }
+    let option = quote! {
+        (.. $args:expr) => (#spread::__overfn_spread($args));
+    };
+    let items = quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types, dead_code)]
+        trait #spread {
+            type Output;
+            fn __overfn_spread(self) -> Self::Output;
+        }
+        #(#impls)*
+    };
+    (option, items)
+}
+
+/// Callable unit struct named after the set, implementing the `Fn` traits for the argument tuple of every overload
+/// with a nameable signature so that `add(1, 2)` works and `add` can be passed as a value. Requires the
+/// `fn_traits` and `unboxed_closures` nightly features in the crate using it.
+#[cfg(feature = "fn_traits")]
+fn callable_struct(name: &Ident, functions: &[FunctionData], least: &FunctionData) -> TokenStream {
+    let impls = functions
+        .iter()
+        .filter_map(|data| {
+            let (types, output) = spread_signature(data)?;
+            let path = call_path(data)?;
+            let fields = (0..types.len()).map(syn::Index::from);
+            let args = quote! { (#(#types,)*) };
+            Some(quote! {
+                impl ::core::ops::FnOnce<#args> for #name {
+                    type Output = #output;
+                    extern "rust-call" fn call_once(self, args: #args) -> #output {
+                        ::core::ops::Fn::call(&self, args)
+                    }
+                }
+                impl ::core::ops::FnMut<#args> for #name {
+                    extern "rust-call" fn call_mut(&mut self, args: #args) -> #output {
+                        ::core::ops::Fn::call(self, args)
+                    }
+                }
+                #[allow(deprecated)]
+                impl ::core::ops::Fn<#args> for #name {
+                    extern "rust-call" fn call(&self, args: #args) -> #output {
+                        #path(#(args.#fields),*)
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if impls.is_empty() {
+        return quote! {};
+    }
+    let vis = least
+        .vis
+        .parse::<TokenStream>()
+        .expect("visibilities are recorded from tokens");
+    quote! {
+        #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy)]
+        #vis struct #name;
+        #(#impls)*
+    }
+}
+
+/// Dispatcher of a set over the dynamic values of a scripting host: `dispatch_add`, generic over the value type and
+/// taking them from any iterator of known length, without allocating, picking the overload by the number of values,
+/// converting them to its arguments with `TryFrom` and its result back with `From`, and `AddDispatchError`, telling a
+/// missing arity from a value that doesn't convert. Only the first overload of every arity whose signature can be named
+/// without lifetimes outside of it takes part.
+#[cfg(feature = "runtime")]
+fn runtime_dispatcher(
+    name: &Ident,
+    functions: &[FunctionData],
+    least: &FunctionData,
+) -> TokenStream {
+    let function = format_ident!("dispatch_{}", name);
+    // Every set gets a dispatcher, so the overloads left out aren't warned about.
+    let (members, _) = nameable_members(&function.to_string(), functions);
+    let members = members
+        .into_iter()
+        .filter(|(_, types, _)| {
+            types
+                .iter()
+                .all(|ty| is_nameable(ty.to_token_stream(), true))
+        })
+        .collect::<Vec<_>>();
+    if members.is_empty() {
+        return quote! {};
+    }
+    let error = format_ident!("{}DispatchError", camel_case(&name.to_string()));
+    let mut bounds = Vec::new();
+    let arms = members
+        .iter()
+        .map(|(data, types, output)| {
+            let path = call_path(data);
+            let len = types.len();
+            let values = (0..len)
+                .map(|i| format_ident!("value_{}", i))
+                .collect::<Vec<_>>();
+            let args = types
+                .iter()
+                .zip(&values)
+                .enumerate()
+                .map(|(i, (ty, value))| {
+                    quote! {
+                        <#ty as ::core::convert::TryFrom<Value>>::try_from(#value)
+                            .map_err(|_| #error::TypeMismatch { index: #i })?
+                    }
+                });
+            bounds.extend(
+                types
+                    .iter()
+                    .map(|ty| quote! { #ty: ::core::convert::TryFrom<Value> }),
+            );
+            bounds.push(quote! { Value: ::core::convert::From<#output> });
+            let call = quote! {
+                return ::core::result::Result::Ok(::core::convert::From::from(#path(#(#args),*)))
+            };
+            match len {
+                0 => quote! { if len == 0 { #call; } },
+                _ => {
+                    let next = values.iter().map(|_| quote! { values.next() });
+                    quote! {
+                        if len == #len {
+                            if let (#(::core::option::Option::Some(#values),)*) = (#(#next,)*) {
+                                #call;
+                            }
+                        }
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let vis = least
+        .vis
+        .parse::<TokenStream>()
+        .expect("visibilities are recorded from tokens");
+    let doc = format!("Error of [`{}`].", function);
+    let arity_doc = format!(
+        "No overload of `{}!` takes `given` values, only the arities in `available`.",
+        name
+    );
+    let arities = members.iter().map(|(_, types, _)| types.len());
+    let unknown_arity = format!("no overload of `{}!` takes {{}} values, only {{:?}}", name);
+    let type_mismatch = format!(
+        "value {{}} doesn't convert to the type of its argument of `{}!`",
+        name
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis enum #error {
+            #[doc = #arity_doc]
+            UnknownArity {
+                given: usize,
+                available: &'static [usize],
+            },
+            /// The value at `index` doesn't convert to the type of its argument.
+            TypeMismatch { index: usize },
+        }
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    Self::UnknownArity { given, available } => {
+                        ::core::write!(f, #unknown_arity, given, available)
+                    }
+                    Self::TypeMismatch { index } => ::core::write!(f, #type_mismatch, index),
+                }
+            }
+        }
+        impl ::core::error::Error for #error {}
+        #[allow(deprecated)]
+        #vis fn #function<Value, Values>(values: Values) -> ::core::result::Result<Value, #error>
+        where
+            Values: ::core::iter::IntoIterator<Item = Value>,
+            Values::IntoIter: ::core::iter::ExactSizeIterator,
+            #(#bounds),*
+        {
+            let mut values = ::core::iter::IntoIterator::into_iter(values);
+            let len = ::core::iter::ExactSizeIterator::len(&values);
+            #(#arms)*
+            ::core::result::Result::Err(#error::UnknownArity {
+                given: len,
+                available: &[#(#arities),*],
+            })
+        }
+    }
+}
+
+/// Extension trait of a method set marked with `ext`, calling its overloads with the method call syntax and the
+/// arguments in a tuple: `Test_test!(test, 2)` becomes `test.test_ov((2,))`. The trait is named after the set
+/// (`TestTestExt`) and implemented for the type through a hidden trait implemented for the argument tuple of every
+/// overload marked with `ext` whose signature can be named, which must all take the receiver the same way.
+///
+/// With `inherent`, the overloads marked with `inherent` are called instead through an inherent method of the type,
+/// `test.test_any((2,))`, bounded by a visible trait for the argument tuples named after the set (`TestTestArgs`), so
+/// the method shows in the documentation of the type.
+fn method_syntax(
+    name: &Ident,
+    functions: &[FunctionData],
+    least: &FunctionData,
+    inherent: bool,
+) -> TokenStream {
+    let members = functions
+        .iter()
+        .filter(|data| match inherent {
+            true => data.inherent,
+            false => data.ext,
+        })
+        // The methods are implemented for one type, that of the first overload with its generic arguments.
+        .filter(|data| data.type_args == least.type_args)
+        .filter_map(|data| Some((data, spread_signature(data)?)))
+        .collect::<Vec<_>>();
+    let item = match inherent {
+        true => "inherent method",
+        false => "extension trait",
+    };
+    let (ty, self_ty) = match &least.arg_type {
+        ArgType::Instance(ty) => (format_ident!("{}", ty), self_type(least, ty)),
+        _ => unreachable!("`ext` is only accepted on methods"),
+    };
+    let Some(((first, _), rest)) = members.split_first() else {
+        return warning(&format!(
+            "none of the `{}` overloads of `{}` has a signature that can be named outside of its impl, \
+             so no {} is generated",
+            match inherent {
+                true => "inherent",
+                false => "ext",
+            },
+            name,
+            item
+        ));
+    };
+    if let Some((other, _)) = rest
+        .iter()
+        .find(|(data, _)| data.receiver != first.receiver)
+    {
+        return warning(&format!(
+            "`{}` and `{}` take the receiver differently, so `{}` gets no {}",
+            first.name, other.name, name, item
+        ));
+    }
+
+    let name = name.to_string();
+    let method = name
+        .strip_prefix(&format!("{}_", ty))
+        .unwrap_or(&name)
+        .to_string();
+    let ext = format_ident!("{}{}Ext", ty, camel_case(&method));
+    let args = match inherent {
+        true => format_ident!("{}{}Args", ty, camel_case(&method)),
+        false => format_ident!("__overfn_args_{}", name),
+    };
+    let method = match inherent {
+        true => format_ident!("{}_any", method),
+        false => format_ident!("{}_ov", method),
+    };
+    let (receiver, receiver_ty) = match first.receiver {
+        Some(ReceiverKind::Value) => (quote! { self }, quote! { #self_ty }),
+        Some(ReceiverKind::Ref) => (quote! { &self }, quote! { &#self_ty }),
+        Some(ReceiverKind::Mut) => (quote! { &mut self }, quote! { &mut #self_ty }),
+        None => unreachable!("methods of inherent impls take a receiver"),
+    };
+    let impls = members.iter().map(|(data, (types, output))| {
+        let func = format_ident!("{}", data.name);
+        let fields = (0..types.len()).map(syn::Index::from);
+        quote! {
+            #[allow(deprecated)]
+            impl #args for (#(#types,)*) {
+                type Output = #output;
+                fn __overfn_call(self, receiver: #receiver_ty) -> Self::Output {
+                    receiver.#func(#(self.#fields),*)
+                }
+            }
+        }
+    });
+    let vis = least
+        .vis
+        .parse::<TokenStream>()
+        .expect("visibilities are recorded from tokens");
+    if inherent {
+        let tuples = members
+            .iter()
+            .map(|(_, (types, _))| format!("`({})`", tidy(&quote! { #(#types,)* }.to_string())))
+            .collect::<Vec<_>>();
+        let doc = format!(
+            "Calls `{}!` with the arguments in a tuple: {}.",
+            name,
+            tuples.join(", ")
+        );
+        let args_doc = format!("Argument tuples of [`{}::{}`].", ty, method);
+        return quote! {
+            #[doc = #args_doc]
+            #vis trait #args {
+                type Output;
+                #[doc(hidden)]
+                fn __overfn_call(self, receiver: #receiver_ty) -> Self::Output;
+            }
+            #(#impls)*
+            impl #self_ty {
+                #[doc = #doc]
+                #vis fn #method<A: #args>(#receiver, args: A) -> A::Output {
+                    args.__overfn_call(self)
+                }
+            }
+        };
+    }
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #vis trait #args {
+            type Output;
+            fn __overfn_call(self, receiver: #receiver_ty) -> Self::Output;
+        }
+        #(#impls)*
+        #vis trait #ext {
+            fn #method<A: #args>(#receiver, args: A) -> A::Output;
+        }
+        impl #ext for #self_ty {
+            fn #method<A: #args>(#receiver, args: A) -> A::Output {
+                args.__overfn_call(self)
+            }
+        }
+    }
+}
+
+/// Checks of the overloads renamed under `config!(inline)` whose signature can be named outside of them: coercing the
+/// path the macro calls to a function pointer of the signature of the overload proves at compile time that the macro
+/// calls the function itself, without an adapter in between.
+fn inline_checks(functions: &[FunctionData]) -> TokenStream {
+    let checks = functions
+        .iter()
+        .filter(|data| data.inline && data.convert != Convert::Pack)
+        .filter_map(|data| {
+            let (types, output) = spread_signature(data)?;
+            let path = call_path(data)?;
+            Some(quote! {
+                #[allow(deprecated)]
+                const _: fn(#(#types),*) -> #output = #path;
+            })
+        });
+    quote! { #(#checks)* }
+}
+
+/// Overloads of a set with the `unify_return` enum given to any of them, so every option of the macro wraps its call.
+fn unified(functions: &[FunctionData]) -> Cow<'_, [FunctionData]> {
+    let Some(unify) = functions.iter().find_map(|data| data.unify_return.as_ref()) else {
+        return Cow::Borrowed(functions);
+    };
+    let functions = functions.iter().map(|data| FunctionData {
+        unify_return: Some(unify.clone()),
+        ..data.clone()
+    });
+    Cow::Owned(functions.collect())
+}
+
+/// Enum named with `unify_return`, with a variant per arity holding the return type of the overloads taking that
+/// many arguments, counting the receiver of methods as the macro does. The return types must be nameable outside of
+/// the overloads and agree between the overloads of an arity.
+fn unified_enum(name: &Ident, functions: &[FunctionData], least: &FunctionData) -> TokenStream {
+    let Some(unify) = &least.unify_return else {
+        return quote! {};
+    };
+    let error = |message: String| syn::Error::new(Span::call_site(), message).into_compile_error();
+    let mut variants = Vec::<(usize, Type)>::new();
+    for data in functions {
+        if data.array_len.is_some() || data.by_literal || data.keywords.is_some() {
+            return error(format!(
+                "`unify_return` can't wrap the calls of `{}`, made with `by_len`, `by_literal` or `keywords`",
+                data.name
+            ));
+        }
+        let Some(output) = nameable_output(data) else {
+            return error(format!(
+                "`unify_return` needs return types that can be named outside of the overloads, unlike the one of `{}`",
+                data.name
+            ));
+        };
+        let same = |other: &Type| {
+            other.to_token_stream().to_string() == output.to_token_stream().to_string()
+        };
+        match variants.iter().find(|(n_args, _)| *n_args == data.n_args) {
+            Some((_, other)) if !same(other) => {
+                return error(format!(
+                    "overloads of `{}!` taking {} argument(s) return different types, which one variant of `{}` \
+                     can't hold",
+                    name, data.n_args, unify
+                ));
+            }
+            Some(_) => {}
+            None => variants.push((data.n_args, output)),
+        }
+    }
+    let unify = format_ident!("{}", unify);
+    let variants = variants.iter().map(|(n_args, output)| {
+        let variant = arity_variant(*n_args);
+        quote! { #variant(#output) }
+    });
+    let vis = least
+        .vis
+        .parse::<TokenStream>()
+        .expect("visibilities are recorded from tokens");
+    quote! {
+        #vis enum #unify {
+            #(#variants),*
+        }
+    }
+}
+
+/// Names of the variants of the enums generated for a set, after the number of arguments of the overloads.
+const ARITY_NAMES: [&str; 13] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten",
+    "Eleven", "Twelve",
+];
+
+/// Variant of a set enum for the overloads taking `n_args` arguments.
+fn arity_variant(n_args: usize) -> Ident {
+    match ARITY_NAMES.get(n_args) {
+        Some(arity) => format_ident!("{}", arity),
+        None => format_ident!("Arity{}", n_args),
+    }
+}
+
+/// Overload with the argument and return types of its signature.
+type Member<'a> = (&'a FunctionData, Vec<Type>, Type);
+
+/// First overload of every arity whose signature can be named outside of it, and warnings for the others, left out of
+/// the `item` generated for the set.
+fn nameable_members<'a>(
+    item: &str,
+    functions: &'a [FunctionData],
+) -> (Vec<Member<'a>>, Vec<TokenStream>) {
+    let mut members = Vec::<Member>::new();
+    let mut warnings = Vec::new();
+    for data in functions {
+        // Packing adapters of `into` only forward to the function they were added for.
+        let taken = members
+            .last()
+            .is_some_and(|(last, ..)| last.n_args == data.n_args);
+        if taken || data.convert == Convert::Pack {
+            continue;
+        }
+        match spread_signature(data).filter(|_| call_path(data).is_some()) {
+            Some((types, output)) => members.push((data, types, output)),
+            None => warnings.push(warning(&format!(
+                "`{}` is left out of `{}`, which only calls functions with a signature that can be named outside of \
+                 them",
+                data.name, item
+            ))),
+        }
+    }
+    (members, warnings)
+}
+
+/// Argument enum and dispatcher function of a set marked with `dispatch`: `AddArgs` with a variant per arity holding the
+/// arguments of the first overload of that arity, and `add_dispatch` calling it. Methods and overloads without a
+/// nameable signature are left out, with a warning.
+fn dispatcher(name: &Ident, functions: &[FunctionData], least: &FunctionData) -> TokenStream {
+    let function = format_ident!("{}_dispatch", name);
+    let (members, warnings) = nameable_members(&function.to_string(), functions);
+    let Some((_, _, output)) = members.first() else {
+        return quote! { #(#warnings)* };
+    };
+    let unify = least
+        .unify_return
+        .as_ref()
+        .map(|unify| format_ident!("{}", unify));
+    let same_output =
+        |other: &Type| other.to_token_stream().to_string() == output.to_token_stream().to_string();
+    let mismatch = members.iter().find(|(.., other)| !same_output(other));
+    if let (None, Some((data, ..))) = (&unify, mismatch) {
+        return warning(&format!(
+            "`{}_dispatch` isn't generated: `{}` doesn't return the same type as the other overloads",
+            name, data.name
+        ));
+    }
+
+    let args = format_ident!("{}Args", camel_case(&name.to_string()));
+    let (variants, arms): (Vec<_>, Vec<_>) = members
+        .iter()
+        .map(|(data, types, _)| {
+            let variant = arity_variant(types.len());
+            let path = call_path(data);
+            let fields = (0..types.len())
+                .map(|i| format_ident!("arg_{}", i))
+                .collect::<Vec<_>>();
+            let (variant_def, pattern) = match types.is_empty() {
+                true => (quote! { #variant }, quote! { #args::#variant }),
+                false => (
+                    quote! { #variant(#(#types),*) },
+                    quote! { #args::#variant(#(#fields),*) },
+                ),
+            };
+            let call = quote! { #path(#(#fields),*) };
+            let call = match &unify {
+                Some(unify) => {
+                    let unified = arity_variant(data.n_args);
+                    quote! { #unify::#unified(#call) }
+                }
+                None => call,
+            };
+            (variant_def, quote! { #pattern => #call })
+        })
+        .unzip();
+    let vis = least
+        .vis
+        .parse::<TokenStream>()
+        .expect("visibilities are recorded from tokens");
+    let output = match &unify {
+        Some(unify) => unify.to_token_stream(),
+        None => output.to_token_stream(),
+    };
+    quote! {
+        #(#warnings)*
+        #vis enum #args {
+            #(#variants),*
+        }
+        #[allow(non_snake_case)]
+        #[allow(deprecated)]
+        #vis fn #function(args: #args) -> #output {
+            match args {
+                #(#arms),*
+            }
+        }
+    }
+}
+
+/// Function pointer table of a set marked with `table`: `ADD_OVERLOADS`, listing the arity of the first overload of
+/// every arity with a pointer to it in `AddOverload`, an enum with a variant per arity holding a function pointer of
+/// its signature. Methods and overloads without a nameable signature are left out, with a warning.
+fn overload_table(name: &Ident, functions: &[FunctionData], least: &FunctionData) -> TokenStream {
+    let table = format_ident!("{}_OVERLOADS", name.unraw().to_string().to_uppercase());
+    let (members, warnings) = nameable_members(&table.to_string(), functions);
+    if members.is_empty() {
+        return quote! { #(#warnings)* };
+    }
+    let pointer = format_ident!("{}Overload", camel_case(&name.to_string()));
+    let (variants, entries): (Vec<_>, Vec<_>) = members
+        .iter()
+        .map(|(data, types, output)| {
+            let variant = arity_variant(types.len());
+            let path = call_path(data);
+            let len = types.len();
+            (
+                quote! { #variant(fn(#(#types),*) -> #output) },
+                quote! { (#len, #pointer::#variant(#path)) },
+            )
+        })
+        .unzip();
+    let vis = least
+        .vis
+        .parse::<TokenStream>()
+        .expect("visibilities are recorded from tokens");
+    quote! {
+        #(#warnings)*
+        #[derive(Clone, Copy, Debug)]
+        #vis enum #pointer {
+            #(#variants),*
+        }
+        #[allow(deprecated)]
+        #vis static #table: &[(usize, #pointer)] = &[#(#entries),*];
+    }
+}
+
+/// Overloads of a set called by a single function taking the arguments of the shortest overload followed by the rest of
+/// the arguments of the longest one as optional, for the bindings of languages without overloading.
+struct TrailingOptional<'a> {
+    /// First overload of every arity with the number of arguments it takes.
+    members: Vec<(&'a FunctionData, usize)>,
+    /// Names of the arguments of the longest overload.
+    names: Vec<Ident>,
+    params: Vec<TokenStream>,
+    output: TokenStream,
+    required: usize,
+}
+
+impl<'a> TrailingOptional<'a> {
+    /// Arguments of `function`, or a warning explaining why it isn't generated: every overload must have a signature
+    /// that can be named, take the same leading arguments and return the same type.
+    fn of(functions: &'a [FunctionData], function: &Ident) -> Result<Self, TokenStream> {
+        let mut members = Vec::<(&FunctionData, Vec<Type>, Type)>::new();
+        for data in functions {
+            let taken = members
+                .last()
+                .is_some_and(|(last, ..)| last.n_args == data.n_args);
+            if taken || data.convert == Convert::Pack {
+                continue;
+            }
+            match spread_signature(data).filter(|_| call_path(data).is_some()) {
+                Some((types, output)) => members.push((data, types, output)),
+                None => {
+                    return Err(warning(&format!(
+                        "`{}` isn't generated: `{}` doesn't have a signature that can be named outside of it",
+                        function, data.name
+                    )))
+                }
+            }
+        }
+        let (Some((shortest, ..)), Some((longest, types, output))) =
+            (members.first(), members.last())
+        else {
+            return Err(quote! {});
+        };
+        let mismatch = members.iter().find(|(data, _, other)| {
+            !longest.arg_types.starts_with(&data.arg_types)
+                || other.to_token_stream().to_string() != output.to_token_stream().to_string()
+        });
+        if let Some((data, ..)) = mismatch {
+            return Err(warning(&format!(
+                "`{}` isn't generated: the arguments of `{}` don't start like those of `{}` or it returns another type",
+                function, data.name, longest.name
+            )));
+        }
+
+        let required = shortest.arg_types.len();
+        let names = longest
+            .arg_names
+            .iter()
+            .map(|name| format_ident!("{}", name))
+            .collect::<Vec<_>>();
+        let params = names
+            .iter()
+            .zip(types)
+            .enumerate()
+            .map(|(i, (name, ty))| match i < required {
+                true => quote! { #name: #ty },
+                false => quote! { #name: ::core::option::Option<#ty> },
+            })
+            .collect();
+        Ok(Self {
+            output: output.to_token_stream(),
+            members: members
+                .iter()
+                .map(|(data, types, _)| (*data, types.len()))
+                .collect(),
+            names,
+            params,
+            required,
+        })
+    }
+
+    /// Match on the optional arguments calling the overload matching the arguments given, and returning `error` for
+    /// argument lists that skip an argument.
+    fn dispatch(&self, error: TokenStream) -> TokenStream {
+        let optional = &self.names[self.required..];
+        let arms = self.members.iter().map(|(data, len)| {
+            let path = call_path(data);
+            let given = &self.names[..*len];
+            let patterns = optional.iter().map(|name| match given.contains(name) {
+                true => quote! { ::core::option::Option::Some(#name) },
+                false => quote! { ::core::option::Option::None },
+            });
+            quote! {
+                (#(#patterns,)*) => ::core::result::Result::Ok(#path(#(#given),*))
+            }
+        });
+        quote! {
+            #[allow(unreachable_patterns)]
+            match (#(#optional,)*) {
+                #(#arms,)*
+                _ => ::core::result::Result::Err(#error),
+            }
+        }
+    }
+}
+
+/// pyo3 function of a set marked with `pyo3`: `add_py`, exposed to Python as `add`, takes the arguments of the shortest
+/// overload followed by the rest of the arguments of the longest one as optional, and calls the overload matching the
+/// arguments given. Arguments after a missing one raise a `TypeError`, as argument lists that match no overload do.
+fn pyo3_function(name: &Ident, functions: &[FunctionData], least: &FunctionData) -> TokenStream {
+    let function = format_ident!("{}_py", name);
+    let trailing = match TrailingOptional::of(functions, &function) {
+        Ok(trailing) => trailing,
+        Err(warning) => return warning,
+    };
+    let signature =
+        trailing
+            .names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| match i < trailing.required {
+                true => quote! { #name },
+                false => quote! { #name = None },
+            });
+    let python_name = name.unraw().to_string();
+    let message = format!("{}() got arguments that match no overload", python_name);
+    let vis = least
+        .vis
+        .parse::<TokenStream>()
+        .expect("visibilities are recorded from tokens");
+    let (params, output) = (&trailing.params, &trailing.output);
+    let body = trailing.dispatch(quote! { ::pyo3::exceptions::PyTypeError::new_err(#message) });
+    quote! {
+        #[::pyo3::pyfunction]
+        #[allow(deprecated)]
+        #[pyo3(name = #python_name, signature = (#(#signature),*))]
+        #[allow(non_snake_case)]
+        #vis fn #function(#(#params),*) -> ::pyo3::PyResult<#output> {
+            #body
+        }
+    }
+}
+
+/// wasm-bindgen function of a set marked with `wasm`: `add_js`, exported to JavaScript as `add`, takes the arguments
+/// like the pyo3 function, the optional ones as `Option` so JavaScript callers can leave them `undefined`, and throws an
+/// error for arguments after a missing one.
+fn wasm_function(name: &Ident, functions: &[FunctionData], least: &FunctionData) -> TokenStream {
+    let function = format_ident!("{}_js", name);
+    let trailing = match TrailingOptional::of(functions, &function) {
+        Ok(trailing) => trailing,
+        Err(warning) => return warning,
+    };
+    let js_name = name.unraw().to_string();
+    let message = format!("{}() got arguments that match no overload", js_name);
+    let vis = least
+        .vis
+        .parse::<TokenStream>()
+        .expect("visibilities are recorded from tokens");
+    let (params, output) = (&trailing.params, &trailing.output);
+    let body = trailing.dispatch(quote! { ::wasm_bindgen::JsError::new(#message) });
+    quote! {
+        #[::wasm_bindgen::prelude::wasm_bindgen(js_name = #js_name)]
+        #[allow(deprecated)]
+        #[allow(non_snake_case)]
+        #vis fn #function(#(#params),*) -> ::core::result::Result<#output, ::wasm_bindgen::JsError> {
+            #body
+        }
+    }
+}
+
+/// Identifier of an item named after the set `name`, raw for keywords so a set of `r#match` generates `r#match!`.
+/// The registry keeps the names without the `r#`, which would end up in the middle of the names built from them.
+pub(crate) fn set_ident(name: &str) -> Ident {
+    syn::parse_str(name).unwrap_or_else(|_| Ident::new_raw(name, Span::call_site()))
+}
+
+/// `add_to_cart` as `AddToCart`, to name generated types after a set.
+fn camel_case(name: &str) -> String {
+    name.trim_start_matches("r#")
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Path calling an overload without a receiver, `None` for methods.
+fn call_path(data: &FunctionData) -> Option<TokenStream> {
+    let func = format_ident!("{}", data.name);
+    let module = module_path(data, &quote! { crate:: });
+    match &data.arg_type {
+        ArgType::Struct(name) => {
+            let name = format_ident!("{}", name);
+            let type_args = data.type_args.as_ref().map(|args| {
+                let args = args
+                    .parse::<TokenStream>()
+                    .expect("generic arguments are recorded from tokens");
+                quote! { ::#args }
+            });
+            Some(quote! { #module #name #type_args::#func })
+        }
+        ArgType::Other => Some(quote! { #module #func }),
+        ArgType::Instance(_) | ArgType::Trait(..) => None,
+    }
+}
+
+/// Prefix of the paths of the items of an overload in a macro: `krate`, or the path of the module of a `module`
+/// overload under the crate, `$crate::` in local macros whose `krate` is empty.
+fn item_prefix(data: &FunctionData, krate: &TokenStream) -> TokenStream {
+    match (&data.module, krate.is_empty()) {
+        (None, _) => krate.clone(),
+        (Some(_), true) => module_path(data, &quote! { $crate:: }),
+        (Some(_), false) => module_path(data, krate),
+    }
+}
+
+/// Path of the module given to an overload with `module = path`, under `krate` (`crate::` in items and `$crate::` in
+/// macros), and nothing for overloads named from the scope of the macro.
+fn module_path(data: &FunctionData, krate: &TokenStream) -> TokenStream {
+    match &data.module {
+        Some(module) => {
+            let module = module
+                .parse::<TokenStream>()
+                .expect("module paths are recorded from tokens");
+            quote! { #krate #module:: }
+        }
+        None => quote! {},
+    }
+}
+
+/// Argument and return types of an overload when they can be named outside of it: no generics, `impl Trait`,
+/// inferred types or elided lifetimes in the return type. A bare `Self` return type is replaced by the type name.
+/// Unsafe overloads are left out too, since the generated items calling them would be safe, and so are overloads of
+/// another module, whose types are written for its scope.
+fn spread_signature(data: &FunctionData) -> Option<(Vec<Type>, Type)> {
+    if data.generic
+        || data.convert == Convert::Pack
+        || data.safety != Safety::Safe
+        || data.module.is_some()
+    {
+        return None;
+    }
+    let types = data
+        .arg_types
+        .iter()
+        .map(|ty| {
+            syn::parse_str::<Type>(ty)
+                .ok()
+                .filter(|ty| is_nameable(ty.to_token_stream(), false))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((types, nameable_output(data)?))
+}
+
+/// Return type of an overload when it can be named outside of it, as for [`spread_signature`].
+fn nameable_output(data: &FunctionData) -> Option<Type> {
+    if data.generic || data.module.is_some() {
+        return None;
+    }
+    match (&data.output, &data.arg_type) {
+        (None, _) => Some(syn::parse_quote! { () }),
+        (Some(output), ArgType::Struct(name) | ArgType::Instance(name)) if output == "Self" => {
+            syn::parse_str(&format!(
+                "{}{}",
+                name,
+                data.type_args.as_deref().unwrap_or_default()
+            ))
+            .ok()
+        }
+        (Some(output), _) => syn::parse_str::<Type>(output)
+            .ok()
+            .filter(|ty| is_nameable(ty.to_token_stream(), true)),
+    }
+}
+
+fn is_nameable(tokens: TokenStream, output: bool) -> bool {
+    tokens.into_iter().all(|token| match token {
+        TokenTree::Ident(ident) => ident != "impl" && ident != "Self" && ident != "_",
+        TokenTree::Punct(punct) => !output || (punct.as_char() != '&' && punct.as_char() != '\''),
+        TokenTree::Group(group) => is_nameable(group.stream(), output),
+        TokenTree::Literal(_) => true,
+    })
+}
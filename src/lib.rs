@@ -58,52 +58,57 @@
 //! assert_eq!(Test_test!(test, 2), 4);
 //! ```
 //!
+//! # `no_std`
+//!
+//! The generated code only names items of `core`, so the overloads and their macros work in `#![no_std]` crates,
+//! including the dispatchers of the `runtime` feature, which don't allocate. Only `catch_unwind`, which catches panics
+//! with the standard library, and the `pyo3` and `wasm` bindings need `std`.
+//!
 //! # Limitations
 //!
 //! - Curretly, you can't overload a function with the same number of arguments with different types.
 //! - You need to use the [`macros!()`](macro@macros) macro to generate the macros to call the overloaded functions.
 //! - If you overload a class method or instance method, you need to pass the class name in the attribute.
-use proc_macro::TokenStream;
-use std::{
-    collections::{HashMap, HashSet},
-    sync::Mutex,
-};
+mod args;
+mod expand;
+mod registry;
 
-use once_cell::sync::Lazy;
-use quote::{format_ident, quote};
-use syn::{parse_macro_input, Ident, ItemFn};
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum ArgType {
-    Struct(String),
-    Instance,
-    Other,
-}
+use proc_macro::TokenStream;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct FunctionData {
-    name: String,
-    n_args: usize,
-    arg_type: ArgType,
-}
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, Parser},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Expr, FnArg, ItemFn, Token, Type,
+};
 
-impl FunctionData {
-    fn new(name: String, arg: ArgType, function: &ItemFn) -> Self {
-        Self {
-            name,
-            n_args: function.sig.inputs.len(),
-            arg_type: arg,
-        }
-    }
-}
-static FUNCTIONS: Lazy<Mutex<HashMap<String, HashSet<FunctionData>>>> =
-    Lazy::new(|| Mutex::new(HashMap::new()));
+use crate::{
+    args::{
+        BorrowedReceiver, ConfigArgs, KeywordCall, LiteralCall, MacrosArgs, OverloadArgs, SetPath,
+        TestSuite, UnparsedFn,
+    },
+    expand::{
+        call_macro, catch_unwind_wrapper, chain_wrapper, default_alias, doc_page, extern_shim,
+        family_docs, imported_macros, module_clash, optional_delegates, overload_macro,
+        persisted_sets, prelude, set_macro, set_marker, shadowing_error, warning,
+    },
+    registry::{
+        content_hash, inconsistencies, inlined, least_visible, mangling, param_names, register,
+        registered, report, ungenerated, with_registry, ArgType, Convert, FunctionData, Location,
+        Mangling, Safety,
+    },
+};
 
 /// Overload a function with a new function with the same name but with different number of arguments.
 ///
 /// After overloading a function, you need to use the [`macros!()`](macro@macros) macro to generate the macros to call the
 /// overloaded functions.
 ///
+/// The attribute applies to free functions and to the methods of an impl, each of which takes its own attribute.
+/// Applying it to any other item, such as a struct or the impl block itself, fails with an error naming the item.
+///
 /// # Example
 ///
 /// ```rust
@@ -158,128 +163,3163 @@ static FUNCTIONS: Lazy<Mutex<HashMap<String, HashSet<FunctionData>>>> =
 /// assert_eq!(Test_test!(test, 2), 4);
 /// ```
 ///
-/// # Limitations
+/// # Arities
 ///
-/// - Curretly, you can't overload a function with the same number of arguments with different types.
-/// - You need to use the [`macros!()`](macro@macros) macro to generate the macros to call the overloaded functions.
-/// - If you overload a class method or instance method, you need to pass the class name in the attribute.
-#[proc_macro_attribute]
-pub fn overload(attr: TokenStream, function: TokenStream) -> TokenStream {
-    let mut function = parse_macro_input!(function as ItemFn);
-
-    let ident = &function.sig.ident;
-    let n_args = &function.sig.inputs.len();
-    let new_ident = format_ident!("{}_{}", ident, n_args, span = ident.span());
-
-    let (arg_type, macro_ident) = match attr.is_empty() {
-        true => (ArgType::Other, ident.to_string()),
-        false => {
-            let struct_name = parse_macro_input!(attr as Ident);
-            let arg_type = match function.sig.inputs.first() {
-                Some(arg) if matches!(arg, syn::FnArg::Receiver(_)) => ArgType::Instance,
-                _ => ArgType::Struct(struct_name.to_string()),
-            };
-            (arg_type, format!("{}_{}", struct_name, ident))
-        }
-    };
-
-    let new = FUNCTIONS
-        .lock()
-        .unwrap()
-        .entry(macro_ident)
-        .or_insert_with(Default::default)
-        .insert(FunctionData::new(
-            new_ident.to_string(),
-            arg_type,
-            &function,
-        ));
-
-    if !new {
-        panic!(
-            "Function {} with {} arguments already exists",
-            ident, n_args
-        );
-    }
-
-    function.sig.ident = new_ident;
-
-    quote! { #function }.into()
-}
-
-/// Generate the macros to call the overloaded functions. You need to call this macro after all the functions are
-/// overloaded.
+/// Functions without arguments can be overloaded too and are called with an empty macro (`init!()`). Calling a macro
+/// with a number of arguments that no overload takes fails with an error listing the numbers of arguments the set
+/// accepts, counting the receiver for methods, and suggesting the overload closest to the number given along with the
+/// names of its parameters.
 ///
-/// For more information, see the [`overload`](macro@overload) macro.
+/// ```compile_fail
+/// use overfn::*;
 ///
-/// # Example
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///     item
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// add!(); // no overload of `add!` takes 0 argument(s): it takes 1 argument(s), did you mean `add!(item)`?
+/// # }
+/// ```
+///
+/// # Optional parameters
+///
+/// Pass `optional_from = N` (`#[overload(optional_from = 1)]`) to write only the overload taking every parameter and
+/// have the attribute also generate the overloads taking the first `N`, `N + 1`, ... of them, each calling the full one
+/// with `Default::default()` for the parameters it leaves out. The generated overloads are renamed after their arity
+/// like any other, share the other arguments of the attribute and keep the documentation of the full overload, while
+/// `default`, `derive_default` and `derive_from` only apply to the full one. The types of the parameters left out
+/// must implement `Default` and can't use generic parameters that no remaining parameter uses, since nothing would
+/// infer them. The option isn't available for methods of trait impls, which can't declare other methods.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(optional_from = 1)]
+/// fn connect(host: &str, port: u16, timeout: Option<u32>) -> String {
+///     format!("{}:{} ({:?})", host, port, timeout)
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(connect!("localhost"), "localhost:0 (None)");
+/// assert_eq!(connect!("localhost", 80), "localhost:80 (None)");
+/// assert_eq!(connect!("localhost", 80, Some(5)), "localhost:80 (Some(5))");
+/// # }
+/// ```
+///
+/// # Keyword arguments
+///
+/// Pass `keywords` to an overload (`#[overload(keywords)]`) to also call it with its arguments given by parameter
+/// name, in any order: `fetch!(url = "/", retries = 2)`. Defaults given as `keywords(timeout = 30)` let the calls leave
+/// those parameters out. A keyword call picks the first overload marked with `keywords` that has a parameter of every
+/// name given and a default for each parameter left out, and fails listing the parameters of each of them otherwise.
+/// The arguments are evaluated in the order they are written and passed as is, while the defaults are evaluated where
+/// the macro is called, so they are best kept to literals and constants. Keyword calls take named parameters of free
+/// and associated functions, and aren't available through exported macros. Parameters taking a pattern, such as
+/// `(x, y): (f32, f32)`, are named by their position among the parameters (`arg_0`), as in the documentation of the
+/// set. Since the macro takes `name = value` arguments as keywords, its calls can't pass assignments as arguments.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(keywords(timeout = 30, retries = 0))]
+/// fn fetch(url: &str, timeout: u32, retries: u8) -> String {
+///     format!("{} ({}s, {} retries)", url, timeout, retries)
+/// }
+///
+/// #[overload(keywords)]
+/// fn fetch(url: &str, body: &str) -> String {
+///     format!("{} <- {}", url, body)
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(fetch!(url = "/"), "/ (30s, 0 retries)");
+/// assert_eq!(fetch!(retries = 2, url = "/"), "/ (30s, 2 retries)");
+/// assert_eq!(fetch!(url = "/", body = "{}"), "/ <- {}");
+/// assert_eq!(fetch!("/", 5, 1), "/ (5s, 1 retries)");
+/// # }
+/// ```
+///
+/// # Recursion
+///
+/// The body of an overload can call the overloads of its own set with `self_overload!(..)`, which the attribute
+/// replaces by a call of the overload taking as many arguments, named like the overload itself: `self_overload!(n, 1)`
+/// in `fn fact(n: u64)` calls `fact_2(n, 1)`. Unlike the macro of the set, it doesn't depend on where
+/// [`macros!()`](macro@macros) is invoked, so it works from any module and with sets that have no macro. Methods and
+/// associated functions call `Self::name_N`, with the receiver passed first and counted like in their macro. The
+/// overload called must share the naming of the caller, so siblings renamed with `mangle = hidden` or told apart by
+/// `by_len`, `by_literal` or `op` can't be called this way, nor can methods of trait impls, which keep their name.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// fn fact(n: u64) -> u64 {
+///     self_overload!(n, 1)
+/// }
+///
+/// #[overload]
+/// fn fact(n: u64, acc: u64) -> u64 {
+///     match n {
+///         0 => acc,
+///         n => self_overload!(n - 1, acc * n),
+///     }
+/// }
+///
+/// assert_eq!(fact_1(5), 120);
+/// ```
+///
+/// # Generic arguments
+///
+/// Overloads only differ by their number of arguments, so they can take `impl Trait` arguments and declare generic
+/// parameters with bounds (`fn log(message: impl Display)` next to `fn log<T: Debug>(level: u8, message: T)`). The
+/// renamed function keeps its signature as written and the macro captures every argument as an `expr`, leaving the
+/// inference of the generic types to the call of the selected overload.
+///
+/// Associated functions of a generic impl (`impl<T: Default> Wrapper<T>`) need `generic` in the attribute
+/// (`#[overload(Wrapper, generic)]`), since the macro can't name `T` outside of the impl to spread typed arguments.
+/// When the type parameters can't be inferred, pass them as a turbofish before the arguments:
+/// `Wrapper_new!(::<u32>)` calls `Wrapper::<u32>::new_0()` and `Wrapper_new!(::<u8>, 5)` calls
+/// `Wrapper::<u8>::new_1(5)`. Macros generated by [`define_overloads!`](macro@define_overloads) for associated
+/// functions accept the same form. Listing the type and const parameters of the impl (`generic(T)`) makes the macro
+/// call the overloads through `Wrapper::<_>::new_1`, with the parameters left to inference, and
+/// [`#[overload_impl]`](macro@overload_impl) on the impl passes them, along with the type, to every overload in it.
+///
+/// Impls of one instance of a generic type, as in typestate builders (`impl Builder<Phase1>`), name its arguments
+/// after the type: `#[overload(Builder<Phase1>)] fn with(x: u8) -> Self` is called as `Builder::<Phase1>::with_1(x)`,
+/// so functions of the same name in the impls of other instances are told apart, and `Self` names `Builder<Phase1>`
+/// in the signatures spread by the macro and in the impls of `derive_default` and `derive_from`.
+///
+/// # Conditional overloads
+///
+/// The compiler evaluates `#[cfg]` attributes before expanding `#[overload]`, wherever they are placed, so overloads
+/// gated behind a disabled `#[cfg(feature = "extended")]` are never registered and the macro has no option for their
+/// arity. Alternatives of the same arity behind exclusive attributes (`#[cfg(unix)]` and `#[cfg(not(unix))]`) can
+/// also share a name, since only one of them registers.
 ///
 /// ```rust
 /// use overfn::*;
 ///
 /// #[overload]
 /// fn add(item: usize) -> usize {
-///    10 + item
+///     item
 /// }
 ///
 /// #[overload]
+/// #[cfg(any())]
 /// fn add(left: usize, right: usize) -> usize {
-///   left + right
+///     left + right
 /// }
 ///
 /// macros!();
 ///
-/// assert_eq!(add!(2), 12);
-/// assert_eq!(add!(2, 2), 4);
+/// assert_eq!(add!(2), 2);
 /// ```
-#[proc_macro]
-pub fn macros(_item: TokenStream) -> TokenStream {
-    let macros = FUNCTIONS
-        .lock()
-        .unwrap()
-        .iter()
-        .map(|(name, functions)| {
-            let options = functions
-                .iter()
-                .map(|data| {
-                    let func = format_ident!("{}", data.name);
-                    let mut func_args = (0..data.n_args)
-                        .map(|i| format_ident!("arg_{}", i))
-                        .map(|arg| (quote! { $ #arg }))
-                        .collect::<Vec<_>>();
-
-                    let input_args = func_args
-                        .iter()
-                        .map(|arg| quote! { #arg: expr })
-                        .collect::<Vec<_>>();
-
-                    let pre_args = match &data.arg_type {
-                        ArgType::Struct(name) => {
-                            let name = format_ident!("{}", name);
-                            quote! { #name:: }
-                        }
-                        ArgType::Instance => {
-                            let self_arg = func_args.remove(0);
-                            quote! { #self_arg. }
-                        }
-                        ArgType::Other => quote! {},
-                    };
-
-                    quote! {
-                        (#(#input_args),*) => (
-                            #pre_args #func(#(#func_args),*)
-                        )
-                    }
-                })
-                .collect::<Vec<_>>();
-            let name = format_ident!("{}", name);
-            quote! {
-                macro_rules! #name {
-                    #(#options);*
-                }
-            }
-        })
-        .map(TokenStream::from)
-        .collect::<TokenStream>();
-    FUNCTIONS.lock().unwrap().clear();
-    macros
+///
+/// # Documentation
+///
+/// Renamed functions are `#[doc(hidden)]` by default, since users are expected to call them through the macro, and
+/// get a `#[doc(alias)]` with the original name. Pass `doc` (`#[overload(doc)]`) to keep them in the documentation:
+/// public overloads then also get an "Overloads of `test`" section, so rustdoc searches for `test` find every `test_N`
+/// and readers see they belong to the same overload set.
+///
+/// # Deprecation
+///
+/// A `#[deprecated]` overload warns where the macro calls it, so `test!(2)` warns while the other arities don't. The
+/// wrappers generated for `chain`, `catch_unwind` and `default` are deprecated along with it, and the items that
+/// [`macros!()`](macro@macros) generates around the set don't warn on their own.
+///
+/// # Visibility
+///
+/// The macro of a set is only as visible as its least visible overload, and [`macros!()`](macro@macros) warns when
+/// the overloads of a set have different visibilities. Pass `inherit_vis` (`#[overload(inherit_vis)]`) to lower the
+/// visibility of a function to the least visible overload registered before it, keeping the whole set consistent.
+///
+/// `macros!(vis = ..)` sets the visibility of the macros it generates instead, without warning, for sets whose callers
+/// can reach every overload anyway, as with `module = path`: `vis = pub(crate)` or `vis = pub(super)` re-exports them
+/// at that visibility, `vis = pub(self)` keeps them private to the module and `vis = pub` exports them from the crate
+/// as if an overload of each set was marked `export`.
+///
+/// # Stateless sets
+///
+/// Passing `set` (`#[overload(set)]`, `#[overload(set = name)]` or `#[overload(Test, set)]`) keeps the overload out of
+/// the registry used by [`macros!()`](macro@macros); its macro is generated by
+/// [`define_overloads!`](macro@define_overloads) instead.
+///
+/// # Conversions
+///
+/// Pass `into` (`#[overload(into)]`) to convert every argument with [`Into::into`] in the macro, so a function taking
+/// a `Config` can be called with anything that converts into it, without writing a generic overload. For functions
+/// taking a single argument, `into(2, 3)` also adds overloads of the listed arities that pack their arguments into a
+/// tuple converted into that argument: `connect!("localhost", 8080)` calls `connect_1(("localhost", 8080).into())`.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// impl From<&str> for Config {
+///     fn from(host: &str) -> Self {
+///         Config { host: host.to_string(), port: 80 }
+///     }
+/// }
+///
+/// impl From<(&str, u16)> for Config {
+///     fn from((host, port): (&str, u16)) -> Self {
+///         Config { host: host.to_string(), port }
+///     }
+/// }
+///
+/// #[overload(into(2))]
+/// fn connect(config: Config) -> String {
+///     format!("{}:{}", config.host, config.port)
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(connect!("localhost"), "localhost:80");
+/// assert_eq!(connect!("localhost", 8080), "localhost:8080");
+/// ```
+///
+/// # Places
+///
+/// Pass `place` with the positions of `&mut` parameters (`#[overload(place(0))]`, counted from 0 after the receiver) to
+/// let callers pass the place itself: the macro borrows the argument mutably, so `fill!(buffer, 0)` calls the overload
+/// with `&mut buffer`. The argument is still captured as an expression, which accepts any place such as `self.data`
+/// or `buffer[2..]`, and the variable stays usable once the call returns.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(place(0))]
+/// fn fill(buffer: &mut [u8], value: u8) {
+///     buffer.fill(value);
+/// }
+///
+/// #[overload(place(0))]
+/// fn fill(buffer: &mut [u8], value: u8, count: usize) {
+///     buffer[..count].fill(value);
+/// }
+///
+/// macros!();
+///
+/// let mut buffer = [0; 4];
+/// fill!(buffer, 1);
+/// fill!(buffer[1..], 2, 2);
+/// assert_eq!(buffer, [1, 2, 2, 1]);
+/// ```
+///
+/// # Trait impls
+///
+/// Methods of trait impls can be overloaded by naming the trait: `#[overload(Money as From<usize>)]`. They keep their
+/// name, since the trait declares it, and the macro calls them through the qualified path
+/// (`<Money as From<usize>>::from(..)`), borrowing the receiver of methods taking `&self` or `&mut self`. This lets
+/// methods of different traits implemented by the same type form a single set, such as `Money_from!`.
+///
+/// # Trait definitions
+///
+/// Methods with a default body in the definition of a trait can be overloaded too, by naming the trait with
+/// `trait_def`: `#[overload(Sensor, trait_def)]`. They are renamed within the trait like the methods of an impl, and
+/// the macro, `Sensor_read!`, calls them as `Sensor::read_2(..)` on any receiver implementing the trait, trait objects
+/// included. Implementations override an overload under its new name. Since the type implementing the trait is only
+/// known at the call, the overloads are treated as generic, and `trait_def` can't be combined with the options
+/// generating items for a type, such as `ext`, `chain` or `default`.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// trait Sensor {
+///     fn level(&self) -> u8;
+///
+///     #[overload(Sensor, trait_def)]
+///     fn read(&self) -> u8 {
+///         self.level()
+///     }
+///
+///     #[overload(Sensor, trait_def)]
+///     fn read(&self, scale: u8) -> u8 {
+///         self.level() * scale
+///     }
+/// }
+///
+/// struct Probe;
+///
+/// impl Sensor for Probe {
+///     fn level(&self) -> u8 {
+///         3
+///     }
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(Sensor_read!(Probe), 3);
+/// let sensor: &dyn Sensor = &Probe;
+/// assert_eq!(Sensor_read!(sensor, 2), 6);
+/// # }
+/// ```
+///
+/// # Constructors
+///
+/// Pass `ctor` to an associated function of a set (`#[overload(Test, ctor)]`, usually on `new`) to also generate a
+/// macro named after the type calling the set, so `Test!(2, 3)` does what `Test_new!(2, 3)` does. The macro only takes
+/// the name of the type in the macro namespace, so the type itself keeps its name.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Color(u8, u8, u8);
+///
+/// impl Color {
+///     #[overload(Color, ctor)]
+///     fn new(gray: u8) -> Self {
+///         Self(gray, gray, gray)
+///     }
+///
+///     #[overload(Color)]
+///     fn new(red: u8, green: u8, blue: u8) -> Self {
+///         Self(red, green, blue)
+///     }
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(Color!(7), Color(7, 7, 7));
+/// assert_eq!(Color!(1, 2, 3), Color(1, 2, 3));
+/// # }
+/// ```
+///
+/// # Enums and unions
+///
+/// The type named in the attribute can be an enum or a union as well as a struct, since the macros only call its
+/// associated functions and methods by path. Constructors named after the variants of an enum
+/// (`#[overload(Shape)] fn Circle(radius: f64) -> Self`) form a set per variant, `Shape_Circle!`, and keep their casing
+/// in the new names without tripping the `non_snake_case` lint.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Shape {
+///     Circle(f64, f64, f64),
+/// }
+///
+/// impl Shape {
+///     #[overload(Shape)]
+///     fn Circle(radius: f64) -> Self {
+///         Self::Circle(0.0, 0.0, radius)
+///     }
+///
+///     #[overload(Shape)]
+///     fn Circle(x: f64, y: f64, radius: f64) -> Self {
+///         Self::Circle(x, y, radius)
+///     }
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(Shape_Circle!(1.0), Shape::Circle(0.0, 0.0, 1.0));
+/// assert_eq!(Shape_Circle!(1.0, 2.0, 3.0), Shape::Circle(1.0, 2.0, 3.0));
+/// # }
+/// ```
+///
+/// # Deriving `Default`
+///
+/// Pass `derive_default` to a constructor without arguments (`#[overload(Test, derive_default)] fn new() -> Self`) to
+/// have [`macros!()`](macro@macros) implement [`Default`] for the type by calling it, keeping `Test::default()` in sync
+/// with `Test_new!()`. The type can't be generic.
+///
+/// # Deriving conversions
+///
+/// Pass `derive_from` to a constructor with one argument (`#[overload(Test, derive_from)] fn new(value: u32) -> Self`)
+/// to have [`macros!()`](macro@macros) implement [`From`] the argument type for the type by calling it, so the
+/// constructor also backs `value.into()`. A constructor returning `Result<Self, E>` implements [`TryFrom`] with `E` as
+/// the error instead, which works with `?` through `try_into()`. The type can't be generic.
+///
+/// # Operators
+///
+/// Pass `op = Add` to a free function with two arguments (`#[overload(op = Add)] fn add(a: Vec2, b: f32) -> Vec2`) to
+/// have [`macros!()`](macro@macros) implement the operator trait of [`core::ops`] for the type of the first argument,
+/// with the type of the second as the right-hand side, so `a + 2.0` calls the same overload as `add!(a, 2.0)`. Overloads
+/// of the same operator can share their arity when their types differ: like with `by_literal`, the function is renamed
+/// after its arity and its types (`add_2_vec2_f32`), and the macro calls the operator trait, which picks the overload
+/// from the types of the arguments. Any binary operator is accepted: `Add`, `Sub`, `Mul`, `Div`, `Rem`, `BitAnd`,
+/// `BitOr`, `BitXor`, `Shl` and `Shr`. The orphan rules apply as for a handwritten impl, so one of the types must be
+/// local.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Vec2(f32, f32);
+///
+/// #[overload(op = Mul)]
+/// fn scale(v: Vec2, factor: f32) -> Vec2 {
+///     Vec2(v.0 * factor, v.1 * factor)
+/// }
+///
+/// #[overload(op = Mul)]
+/// fn scale(v: Vec2, other: Vec2) -> Vec2 {
+///     Vec2(v.0 * other.0, v.1 * other.1)
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(Vec2(1.0, 2.0) * 2.0, Vec2(2.0, 4.0));
+/// assert_eq!(Vec2(1.0, 2.0) * Vec2(3.0, 4.0), scale!(Vec2(1.0, 2.0), Vec2(3.0, 4.0)));
+/// # }
+/// ```
+///
+/// # Counting `self`
+///
+/// The receiver of instance methods counts as an argument, so `fn test(&self, other: usize)` becomes `test_2`. Pass
+/// `skip_self` (`#[overload(Test, skip_self)]`) to name it after the explicit arguments only (`test_1`). The macro still
+/// takes the receiver as its first argument: `Test_test!(test, 2)`.
+///
+/// Methods are always registered with their type, so their macros (`Test_test!`) never take in the free functions of
+/// the same name and arity unless grouped explicitly with `overload_set`. A method marked with a bare `#[overload]` is
+/// rejected instead of being called as a free function:
+///
+/// ```compile_fail
+/// use overfn::*;
+///
+/// struct Test;
+///
+/// impl Test {
+///     #[overload] // `test` takes a receiver, so it needs the type of its impl: `#[overload(Type)]`
+///     fn test(&self) -> usize {
+///         1
+///     }
+/// }
+/// ```
+///
+/// # Receivers
+///
+/// By default, the macro calls methods through their qualified path (`Test::test_2(&test, 2)`), so the method is
+/// always the overload of the type even when the receiver is an arbitrary expression (`Test_test!(get_test()?, 2)`),
+/// and the type must be in scope where the macro is called, as for associated functions. The receiver is still
+/// handled as in a method call: `&self` and `&mut self` borrow it, while `self` moves it unless its type is `Copy`,
+/// and the receiver can be a place (`Test_bump!(test, 1)`, `Test_bump!(holder.test, 1)`), a reference
+/// (`Test_bump!(&mut test, 1)`), a `&mut` binding that isn't `mut` itself, which is reborrowed, or a smart pointer,
+/// which is dereferenced. As with the method call syntax, only the place is borrowed, so `&mut self` methods can be
+/// called on a field while other fields of the same value are borrowed. Pass `receiver = method`
+/// (`#[overload(Test, receiver = method)]`) to a method of an inherent impl to call it with the method call syntax
+/// (`test.test_2(2)`) instead, which doesn't need the type in scope but lets auto-deref pick a method of another type
+/// with the same name. Either way the receiver is parenthesized in the expansion, so casts (`&test as &Test`),
+/// dereferences, `?` and `.await` apply to the receiver as written.
+///
+/// Pass `receiver = copy` (`#[overload(Point, receiver = copy)]`) to call the method on a copy of the receiver
+/// instead. The variable is then never borrowed by the call, so `&mut self` methods change the copy and the result
+/// can't borrow from the receiver. Since the macro can't tell whether a type is `Copy`, calling it with a receiver
+/// that isn't fails to compile rather than cloning or moving it.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[derive(Clone, Copy)]
+/// struct Point(i32, i32);
+///
+/// impl Point {
+///     #[overload(Point, receiver = copy)]
+///     fn moved(&mut self, dx: i32, dy: i32) -> Point {
+///         self.0 += dx;
+///         self.1 += dy;
+///         *self
+///     }
+/// }
+///
+/// macros!();
+///
+/// let origin = Point(0, 0);
+/// let moved = Point_moved!(origin, 1, 2);
+/// assert_eq!((origin.0, moved.0, moved.1), (0, 1, 2));
+/// ```
+///
+/// # Method call syntax
+///
+/// Methods marked with `ext` (`#[overload(Test, ext)]`) can also be called with the method call syntax through an
+/// extension trait generated by [`macros!()`](macro@macros) next to the macro, named after the set (`TestTestExt` for
+/// `Test_test!`) and taking the arguments as a tuple: `test.test_ov((2,))` calls the same overload as
+/// `Test_test!(test, 2)`. Only overloads whose signature can be named outside of the impl are part of the trait, and
+/// they must all take the receiver the same way, since the trait method takes it like them. The trait has the
+/// visibility of the least visible overload and has to be in scope where it is used.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// struct Test(usize);
+///
+/// impl Test {
+///     #[overload(Test, ext)]
+///     fn test(&self) -> usize {
+///         self.0
+///     }
+///
+///     #[overload(Test, ext)]
+///     fn test(&self, other: usize) -> usize {
+///         self.0 + other
+///     }
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// let test = Test(1);
+/// assert_eq!(test.test_ov(()), 1);
+/// assert_eq!(test.test_ov((2,)), 3);
+/// # }
+/// ```
+///
+/// Mark them with `inherent` instead (`#[overload(Test, inherent)]`) to call them through an inherent method of the
+/// type, `test.test_any((2,))`, which needs no import and shows in the documentation of the type with the argument
+/// tuples it accepts. Its arguments are bounded by a trait named after the set (`TestTestArgs`), implemented for the
+/// argument tuple of every overload marked with `inherent`, under the same conditions as `ext`.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// struct Test(usize);
+///
+/// impl Test {
+///     #[overload(Test, inherent)]
+///     fn test(&self) -> usize {
+///         self.0
+///     }
+///
+///     #[overload(Test, inherent)]
+///     fn test(&self, other: usize) -> usize {
+///         self.0 + other
+///     }
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// let test = Test(1);
+/// assert_eq!(test.test_any(()), 1);
+/// assert_eq!(test.test_any((2,)), 3);
+/// # }
+/// ```
+///
+/// # Chaining
+///
+/// Pass `chain` (`#[overload(Builder, chain)]`) on a method taking `&self` or `&mut self` and returning `()` to make the
+/// macro return the receiver, so overloads like `with_header(&mut self, key, value)` and `with_header(&mut self, pair)`
+/// chain the same way: `Builder_with_header!(builder, "a", "1").build()`. The macro calls a hidden
+/// `with_header_3_chain` wrapper generated next to the method.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[derive(Default)]
+/// struct Request {
+///     headers: Vec<(String, String)>,
+/// }
+///
+/// impl Request {
+///     #[overload(Request, chain)]
+///     fn with_header(&mut self, key: &str, value: &str) {
+///         self.headers.push((key.to_string(), value.to_string()));
+///     }
+///
+///     #[overload(Request, chain)]
+///     fn with_header(&mut self, (key, value): (&str, &str)) {
+///         self.headers.push((key.to_string(), value.to_string()));
+///     }
+/// }
+///
+/// macros!();
+///
+/// let mut request = Request::default();
+/// let request = Request_with_header!(Request_with_header!(request, "a", "1"), ("b", "2"));
+/// assert_eq!(request.headers.len(), 2);
+/// ```
+///
+/// # Catching panics
+///
+/// Pass `catch_unwind` (`#[overload(catch_unwind)]`) to make the macro call the overload inside
+/// [`std::panic::catch_unwind`], returning [`std::thread::Result`] with the panic payload as the error instead of
+/// unwinding into the caller, for overloads called at FFI or plugin boundaries. The macro calls a hidden
+/// `add_2_catch_unwind` wrapper generated next to the function, which can also be called directly. The arguments are
+/// evaluated before calling the wrapper, so only panics of the overload itself are caught.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(catch_unwind)]
+/// fn divide(left: u32, right: u32) -> u32 {
+///     left / right
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(divide!(6, 3).unwrap(), 2);
+/// assert!(divide!(1, 0).is_err());
+/// ```
+///
+/// # Unsafe functions
+///
+/// Overloads of an `unsafe fn` stay unsafe and the macro expands to the bare call, so the caller writes the `unsafe`
+/// block around `read!(ptr)` as it would around `read_1(ptr)`. Pass `wrap_unsafe` (`#[overload(wrap_unsafe)]`) to
+/// have the macro wrap the call in an `unsafe` block instead, for functions whose safety contract the set upholds.
+/// Unsafe overloads are left out of spreading non-literal arguments and can't be combined with `ext`, `dispatch`,
+/// `table`, `pyo3`, `wasm` or `by_len`, which generate safe items calling them.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// unsafe fn read(ptr: *const u8) -> u8 {
+///     *ptr
+/// }
+///
+/// #[overload]
+/// unsafe fn read(ptr: *const u8, offset: usize) -> u8 {
+///     *ptr.add(offset)
+/// }
+///
+/// macros!();
+///
+/// let bytes = [1, 2];
+/// assert_eq!(unsafe { read!(bytes.as_ptr()) }, 1);
+/// assert_eq!(unsafe { read!(bytes.as_ptr(), 1) }, 2);
+/// ```
+///
+/// # Exporting
+///
+/// Pass `export` to any overload of a set (`#[overload(export)]`) to also export its macro from the crate with
+/// `#[macro_export]`, so other crates can call `mylib::add!(..)`. The exported macro calls the overloads through
+/// `$crate`, which requires `macros!()` to be invoked at the crate root (or the overloads to pass `module`) and the
+/// overloads to be `pub`. Overloads marked `internal` (`#[overload(internal)]`) are left out of the exported macro and
+/// only reachable through the crate-local one, to keep experimental arities private. Inside the crate, `add!` keeps
+/// calling every overload; paths like `crate::add!` resolve to the exported macro.
+///
+/// # Module paths
+///
+/// The macros call the overloads by their bare name, so they only resolve where the renamed functions are in scope.
+/// Pass the path of the module defining an overload, from the crate root, with `module = path`
+/// (`#[overload(module = shapes)]` or `#[overload(Square, module = crate::shapes)]`) to have the macros call it
+/// through `$crate::shapes::perimeter_1` instead. The macro then works anywhere in the crate, the module doesn't need
+/// to import anything, and an exported macro can be generated outside of the crate root. The renamed functions and
+/// their types must be visible from the module of the `macros!()`. Since the argument types are written for the
+/// scope of the module, these overloads can't be spread from non-literal arguments nor derive conversions.
+///
+/// # Keeping the original name
+///
+/// Renaming removes the original function, so callers that haven't moved to the macro stop compiling. Pass `default` to
+/// one overload of a set (`#[overload(default)]`) to also keep a function under the original name calling it, with the
+/// documentation of the overload: `test(2)` keeps working next to `test!(2, 3)`. Methods and associated functions get
+/// the alias in their impl. It isn't available for methods of trait impls, which keep their name anyway, nor for free
+/// functions of stateless sets, whose macro is re-exported under the same name.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(default)]
+/// fn test(item: usize) -> usize {
+///     item
+/// }
+///
+/// #[overload]
+/// fn test(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(test(2), 2);
+/// assert_eq!(test!(2, 3), 5);
+/// ```
+///
+/// # Renaming
+///
+/// Overloads are renamed after their arity (`test_2`), which can clash with a handwritten function of that name in the
+/// same scope. Pass `mangle = hidden` (`#[overload(mangle = hidden)]`) to rename the function to `__overfn_test_2`
+/// instead, and the macro calls it under that name. It doesn't apply to methods of trait impls, which keep their name,
+/// or to the stateless sets of [`define_overloads!`](macro@define_overloads), which look the overloads up by their
+/// default name. [`config!`](macro@config) changes the naming scheme of every overload of the crate instead.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// fn test_2(left: usize, right: usize) -> usize {
+///     left * right
+/// }
+///
+/// #[overload(mangle = hidden)]
+/// fn test(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(test!(2, 3), 5);
+/// assert_eq!(test_2(2, 3), 6);
+/// ```
+///
+/// Overloads can also be written by other macros, from a `macro_rules!` template adding `#[overload]` to the functions
+/// it declares to a procedural macro. The new names resolve at the call site of the attribute, so the macros and
+/// normal code can call them even when the original names were hygienic.
+///
+/// Raw identifiers lose their `r#` in the new names, which are no longer keywords: `fn r#match` becomes `match_2`, and
+/// `r#Token::r#type` is called by `Token_type!`. A set named after a keyword keeps it in its macro, `r#match!`.
+///
+/// # Renaming without a macro
+///
+/// Pass `no_macro` (`#[overload(no_macro)]`) to only rename the function after its arity, for stable symbol names,
+/// and leave it out of the macro. It is still registered, so it clashes with another overload of the same arity, but
+/// [`macros!()`](macro@macros) doesn't generate anything for it, and a set whose overloads are all marked `no_macro`
+/// has no macro at all and needs no `macros!()`. The options generating items for the set, such as `export` or
+/// `dispatch`, can't be combined with it.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// fn hash(data: &[u8]) -> u8 {
+///     hash_2(data, 0)
+/// }
+///
+/// #[overload(no_macro)]
+/// fn hash(data: &[u8], seed: u8) -> u8 {
+///     data.iter().fold(seed, |total, byte| total.wrapping_add(*byte))
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(hash!(@arities), [1]);
+/// assert_eq!(hash!(&[1, 2]), 3);
+/// assert_eq!(hash_2(&[1, 2], 1), 4);
+/// ```
+///
+/// # C exports
+///
+/// Pass `extern_name = "mylib_test"` to also export an overload to C as an unmangled `pub extern "C"` function named
+/// after the arity like the renamed function, `mylib_test_2` for `test_2`, so a C header can declare every overload of
+/// the set. The Rust functions keep their mangled names and the macro calls them as before. Associated functions get
+/// the export in their impl; methods taking a receiver, generic functions and methods of trait impls can't be exported.
+/// The argument and return types should be FFI-safe, which the compiler checks as for any `extern "C"` function. The
+/// exports are marked `#[unsafe(no_mangle)]`, which needs Rust 1.82 or newer in the crate declaring them.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(extern_name = "mylib_add")]
+/// fn add(left: i32, right: i32) -> i32 {
+///     left + right
+/// }
+///
+/// #[overload(extern_name = "mylib_add")]
+/// fn add(left: i32, middle: i32, right: i32) -> i32 {
+///     left + middle + right
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(add!(1, 2), 3);
+/// assert_eq!(mylib_add_3(1, 2, 3), 6);
+/// ```
+///
+/// # Aliases
+///
+/// Pass `alias = "name"` (`#[overload(alias = "show")]`) to also register the overload in the set of the macro
+/// `name!`, next to its own set, so one function is called by two macros; repeat it for more aliases. Each set is
+/// checked for overloads of the same arity on its own, and the alias sets group their functions like the sets of
+/// [`overload_set`](macro@overload_set), taking free functions and methods of any name.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(alias = "show")]
+/// fn render(value: u32) -> String {
+///     value.to_string()
+/// }
+///
+/// #[overload]
+/// fn render(value: u32, width: usize) -> String {
+///     format!("{:>1$}", value, width)
+/// }
+///
+/// #[overload]
+/// fn show(value: u32, prefix: &str, suffix: &str) -> String {
+///     format!("{}{}{}", prefix, value, suffix)
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(show!(7), render!(7));
+/// assert_eq!(show!(7, "<", ">"), "<7>");
+/// assert_eq!(render!(7, 3), "  7");
+/// # }
+/// ```
+///
+/// # Overriding
+///
+/// Registering two overloads with the same number of arguments under the same name fails with an error at the new
+/// definition, followed by a second one naming the location of the first. Pass `override` to the attribute
+/// (`#[overload(override)]` or `#[overload(Test, override)]`) to intentionally replace the earlier registration, so the
+/// generated macro calls the newer function. When the two overloads take different types (`add(usize)` and
+/// `add(&str)`), the error shows both signatures, since the macro only tells overloads apart by their arity.
+///
+/// ```compile_fail
+/// use overfn::*;
+///
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///     item
+/// }
+///
+/// #[overload]
+/// fn add(other: usize) -> usize {
+///     other + 1
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Expanding the same item again, as some tools do when they re-run attribute macros in the same process, is
+/// recognized by its location and content and doesn't count as a redefinition.
+///
+/// # Priority
+///
+/// The options of a macro are ordered by arity and, among overloads of the same arity, by `priority = N` (`0` by
+/// default, the highest first), so the option that wins doesn't depend on the order in which the compiler expands the
+/// attributes. Overloads of the same arity can be registered together when their priorities differ, and only the one
+/// with the highest priority is called: an explicit `fn open(host: &str, port: u16)` with `priority = 1` takes over
+/// the arity-2 option that `#[overload(into(2))]` adds to `fn open(config: Config)`.
+///
+/// # Dispatcher functions
+///
+/// Pass `dispatch` to any overload of a set (`#[overload(dispatch)]`) to also generate, next to the macro, an enum
+/// with a variant per arity holding the arguments (`AddArgs::Two(1, 2)`) and a function matching on it (`add_dispatch`),
+/// for callers that need a real function to store in function pointers or tables. The variants are named after the
+/// number of arguments (`Zero`, `One`, `Two`, ...) and hold the arguments of the overload the macro calls for that
+/// arity. Methods and overloads whose signature can't be named outside of the function are left out, and the
+/// overloads must all return the same type. The enum and the function have the visibility of the least visible
+/// overload.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(dispatch)]
+/// fn add(item: usize) -> usize {
+///     10 + item
+/// }
+///
+/// #[overload]
+/// fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// let table: [fn(AddArgs) -> usize; 1] = [add_dispatch];
+/// assert_eq!(table[0](AddArgs::One(2)), 12);
+/// assert_eq!(add_dispatch(AddArgs::Two(2, 3)), 5);
+/// ```
+///
+/// # Unified return types
+///
+/// Pass `unify_return = Name` to any overload of a set to have the macro wrap the result of every call in a variant
+/// of `Name`, an enum generated next to the macro with a variant per arity named like the variants of `dispatch`
+/// (`Zero`, `One`, `Two`, ...) and holding the return type of the overloads taking that many arguments, for consumers
+/// that handle the results of all the overloads alike. The return types must be nameable outside of the overloads and
+/// agree between the overloads of an arity, and sets told apart with `by_len` or `by_literal` can't be unified. With
+/// `dispatch`, the dispatcher function returns the enum, so the overloads can return different types. The enum has the
+/// visibility of the least visible overload and is looked up from the crate root in exported macros.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(unify_return = Parsed)]
+/// fn parse(text: &str) -> i64 {
+///     text.parse().unwrap()
+/// }
+///
+/// #[overload]
+/// fn parse(text: &str, radix: u32) -> Option<i64> {
+///     i64::from_str_radix(text, radix).ok()
+/// }
+///
+/// macros!();
+///
+/// for parsed in [parse!("42"), parse!("ff", 16)] {
+///     match parsed {
+///         Parsed::One(value) => assert_eq!(value, 42),
+///         Parsed::Two(value) => assert_eq!(value, Some(255)),
+///     }
+/// }
+/// ```
+///
+/// # Function pointer tables
+///
+/// Pass `table` to any overload of a set (`#[overload(table)]`) to also generate a static table of function pointers to
+/// its overloads, for registering the set into the builtins of an interpreter or any other table built at runtime.
+/// `ADD_OVERLOADS` is a `&[(usize, AddOverload)]` listing every arity with a pointer to the overload the macro calls
+/// for it, and `AddOverload` has a variant per arity, named like the variants of `dispatch`, holding a function pointer
+/// of its signature. Like `dispatch`, it leaves out methods and overloads whose signature can't be named outside of the
+/// function, but the overloads can return different types. Both items have the visibility of the least visible
+/// overload.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(table)]
+/// fn add(item: usize) -> usize {
+///     10 + item
+/// }
+///
+/// #[overload]
+/// fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// let calls = ADD_OVERLOADS.iter().map(|(arity, overload)| match overload {
+///     AddOverload::One(add) => (*arity, add(2)),
+///     AddOverload::Two(add) => (*arity, add(2, 3)),
+/// });
+/// assert_eq!(calls.collect::<Vec<_>>(), [(1, 12), (2, 5)]);
+/// ```
+///
+/// # Python
+///
+/// Pass `pyo3` to any free function of a set (`#[overload(pyo3)]`) to also generate, next to the macro, a pyo3 function
+/// `add_py` exposed to Python as `add`. It takes the arguments of the shortest overload followed by the remaining
+/// arguments of the longest one as optional (`signature = (left, right = None, other = None)`), named like in the
+/// longest overload, and calls the overload matching the arguments given. Argument lists that skip an argument or match
+/// no overload raise a `TypeError`. The overloads must take the same leading arguments and return the same type, and
+/// the crate calling [`macros!()`](macro@macros) must depend on `pyo3`. Register the function in the module as usual:
+///
+/// ```rust,ignore
+/// use overfn::*;
+/// use pyo3::prelude::*;
+///
+/// #[overload(pyo3)]
+/// pub fn add(left: usize) -> usize {
+///     10 + left
+/// }
+///
+/// #[overload]
+/// pub fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// #[pymodule]
+/// fn mylib(module: &Bound<'_, PyModule>) -> PyResult<()> {
+///     module.add_function(wrap_pyfunction!(add_py, module)?)
+/// }
+/// ```
+///
+/// # JavaScript
+///
+/// Pass `wasm` to any free function of a set (`#[overload(wasm)]`) to also generate a wasm-bindgen function `add_js`
+/// exported to JavaScript as `add`, since JavaScript has no arity-based names. It takes the arguments like the pyo3
+/// function, with the optional ones as `Option` so callers can leave them out, calls the overload matching the
+/// arguments given, and throws a `JsError` for argument lists that skip an argument. The same restrictions apply, and
+/// the crate calling [`macros!()`](macro@macros) must depend on `wasm-bindgen`.
+///
+/// ```rust,ignore
+/// use overfn::*;
+///
+/// #[overload(wasm)]
+/// pub fn add(left: u32) -> u32 {
+///     10 + left
+/// }
+///
+/// #[overload]
+/// pub fn add(left: u32, right: u32) -> u32 {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// // JavaScript: add(1) === 11, add(1, 2) === 3
+/// ```
+///
+/// # Array lengths
+///
+/// Overloads of the same arity can differ in the length of an array parameter when they pass `by_len`
+/// (`#[overload(by_len)]`): the function is renamed after its arity and the length (`hash_1_16`), and the macro passes
+/// the arguments as a tuple to a trait implemented for the argument tuple of each overload, so the type of the argument
+/// picks the overload. The length must be an integer literal, the function can't take a receiver or generic
+/// parameters, and every overload of that arity must pass `by_len`. These overloads are left out of exported macros.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(by_len)]
+/// fn hash(data: [u8; 2]) -> u8 {
+///     data[0] ^ data[1]
+/// }
+///
+/// #[overload(by_len)]
+/// fn hash(data: [u8; 4]) -> u8 {
+///     data[0] ^ data[1] ^ data[2] ^ data[3]
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(hash!([1, 2]), 3);
+/// let key = [1, 2, 4, 8];
+/// assert_eq!(hash!(key), 15);
+/// ```
+///
+/// # Literal types
+///
+/// Overloads of the same arity with different parameter types can be registered together when they pass `by_literal`
+/// (`#[overload(by_literal)]`): the function is renamed after its arity and its types (`parse_1_str`), and a call
+/// whose arguments are all literals picks the first overload whose types fit them, told apart by their kind and suffix.
+/// Unsuffixed integers fit every integer type and unsuffixed floats both float types, so `2u64` and `"2"` pick
+/// different overloads while `2` picks the first integer one. Other arguments, and literals no overload fits, call the
+/// first overload of the arity. The function can't take a receiver, and literal calls aren't available through
+/// exported macros.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(by_literal)]
+/// fn parse(value: u64) -> u64 {
+///     value
+/// }
+///
+/// #[overload(by_literal)]
+/// fn parse(value: &str) -> u64 {
+///     value.parse().unwrap()
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(parse!(2u64), 2);
+/// assert_eq!(parse!("3"), 3);
+/// ```
+///
+/// # Limitations
+///
+/// - Curretly, you can't overload a function with the same number of arguments with different types, other than
+///   arrays of different lengths with `by_len`, literal arguments with `by_literal` and operators with `op`.
+/// - You need to use the [`macros!()`](macro@macros) macro to generate the macros to call the overloaded functions.
+/// - If you overload a class method or instance method, you need to pass the class name in the attribute, with its
+///   generic arguments for an impl of one instance of a generic type.
+#[proc_macro_attribute]
+pub fn overload(attr: TokenStream, function: TokenStream) -> TokenStream {
+    let content = content_hash(&attr.to_string(), &function.to_string());
+    let function = match function_item(function, "overload") {
+        Ok(function) => function,
+        Err(error) => return error.into_compile_error().into(),
+    };
+    overload_items(attr, function, content, OverloadArgs::parse)
+}
+
+/// Overload a function under a macro name of its own, grouping functions with different names and owners in one set:
+/// `#[overload_set("len")] fn str_len(text: &str)` is called by `len!(text)`, like the methods registered with
+/// `#[overload_set("len", Stack)]`. The name is followed by the arguments of [`overload`](macro@overload).
+///
+/// Every function is renamed after its own name and arity (`str_len_1`) and adds an option to the macro, which still
+/// selects it by the number of arguments: two overloads of the set can only share an arity with different priorities.
+/// Methods keep taking the receiver as their first argument.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload_set("len")]
+/// fn str_len(text: &str) -> usize {
+///     text.chars().count()
+/// }
+///
+/// struct Stack(Vec<usize>);
+///
+/// impl Stack {
+///     #[overload_set("len", Stack)]
+///     fn depth(&self, limit: usize) -> usize {
+///         self.0.len().min(limit)
+///     }
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(len!("añb"), 3);
+/// assert_eq!(len!(Stack(vec![1, 2, 3]), 2), 2);
+/// # }
+/// ```
+#[proc_macro_attribute]
+pub fn overload_set(attr: TokenStream, function: TokenStream) -> TokenStream {
+    let content = content_hash(&attr.to_string(), &function.to_string());
+    let function = match function_item(function, "overload_set") {
+        Ok(function) => function,
+        Err(error) => return error.into_compile_error().into(),
+    };
+    overload_items(attr, function, content, OverloadArgs::parse_set)
+}
+
+/// Complete the [`overload`](macro@overload) and [`overload_set`](macro@overload_set) attributes of the methods of an
+/// impl with the type of the impl (`Type as Trait` for trait impls) and, for generic impls, `generic` with the type and
+/// const parameters of the impl, so `#[overload]` is enough on each method. Arguments already given are kept. The
+/// generic arguments of an impl of one instance of a type (`impl Builder<Phase1>`) are kept with the type.
+///
+/// With the parameters recorded, the macros call the overloads through the type with a placeholder for each of them
+/// (`Stack::<_>::push_2`), leaving them to inference, and still accept the turbofish form of `generic` for associated
+/// functions whose parameters can't be inferred.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// struct Stack<T>(Vec<T>);
+///
+/// #[overload_impl]
+/// impl<T: Clone> Stack<T> {
+///     #[overload]
+///     fn new() -> Self {
+///         Stack(Vec::new())
+///     }
+///
+///     #[overload]
+///     fn new(item: T, count: usize) -> Self {
+///         Stack(vec![item; count])
+///     }
+///
+///     #[overload]
+///     fn push(&mut self, item: T) {
+///         self.0.push(item);
+///     }
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// let mut stack = Stack_new!('a', 2);
+/// Stack_push!(stack, 'b');
+/// assert_eq!(stack.0, ['a', 'a', 'b']);
+/// assert!(Stack_new!(::<u8>).0.is_empty());
+/// # }
+/// ```
+#[proc_macro_attribute]
+pub fn overload_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    if !attr.is_empty() {
+        return syn::Error::new_spanned(attr, "`overload_impl` takes no arguments")
+            .into_compile_error()
+            .into();
+    }
+    // Only the header of the impl is parsed, the methods are scanned for their attributes and left as tokens.
+    let item = proc_macro2::TokenStream::from(item);
+    let mut tokens = item.clone().into_iter().collect::<Vec<_>>();
+    let body = match tokens.pop() {
+        Some(proc_macro2::TokenTree::Group(body))
+            if body.delimiter() == proc_macro2::Delimiter::Brace =>
+        {
+            body
+        }
+        _ => return full_impl_error(item),
+    };
+    let header = tokens
+        .iter()
+        .cloned()
+        .chain([proc_macro2::TokenTree::Group(proc_macro2::Group::new(
+            proc_macro2::Delimiter::Brace,
+            proc_macro2::TokenStream::new(),
+        ))])
+        .collect::<proc_macro2::TokenStream>();
+    let Ok(header) = syn::parse2::<syn::ItemImpl>(header) else {
+        return full_impl_error(item);
+    };
+    match impl_overloads(&header, body.stream()) {
+        Ok(stream) => {
+            let mut completed = proc_macro2::Group::new(proc_macro2::Delimiter::Brace, stream);
+            completed.set_span(body.span());
+            tokens.push(proc_macro2::TokenTree::Group(completed));
+            tokens
+                .into_iter()
+                .collect::<proc_macro2::TokenStream>()
+                .into()
+        }
+        Err(error) => error.into_compile_error().into(),
+    }
+}
+
+/// Error of an item given to `overload_impl` that isn't an impl, from parsing it whole.
+fn full_impl_error(item: proc_macro2::TokenStream) -> TokenStream {
+    let error = match syn::parse2::<syn::ItemImpl>(item) {
+        Ok(item) => syn::Error::new_spanned(item, "expected an impl block"),
+        Err(error) => error,
+    };
+    error.into_compile_error().into()
+}
+
+/// Arguments of the overload attributes of the methods in the `body` of an impl marked with `overload_impl`, completed
+/// with the type of the impl, its trait and the parameters of a generic impl. The attributes are found among the
+/// tokens of the body, whose items aren't parsed.
+fn impl_overloads(
+    item: &syn::ItemImpl,
+    body: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    use proc_macro2::TokenTree;
+
+    let ty = match &*item.self_ty {
+        Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last(),
+        _ => None,
+    };
+    let Some(segment) = ty else {
+        return Err(syn::Error::new_spanned(
+            &item.self_ty,
+            "`overload_impl` needs an impl of a type named by a path",
+        ));
+    };
+    // The arguments of an impl for a concrete instance of a type, such as `Builder<Phase1>`, can be named in the
+    // macros, while the parameters of a generic impl are left to inference.
+    let ty = &segment.ident;
+    let type_args = match (&segment.arguments, item.generics.params.is_empty()) {
+        (syn::PathArguments::AngleBracketed(args), true) => Some(args),
+        _ => None,
+    };
+    let owner = match &item.trait_ {
+        Some((_, path, _)) => quote! { #ty #type_args as #path },
+        None => quote! { #ty #type_args },
+    };
+    let params = item
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(param) => Some(&param.ident),
+            syn::GenericParam::Const(param) => Some(&param.ident),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    // Only the attributes naming `overload` or `overload_set` are parsed.
+    let overload = |group: &proc_macro2::Group| {
+        group.delimiter() == proc_macro2::Delimiter::Bracket
+            && matches!(group.stream().into_iter().next(),
+                Some(TokenTree::Ident(ident)) if ident == "overload" || ident == "overload_set")
+    };
+    let tokens = body.into_iter().collect::<Vec<_>>();
+    let mut completed = proc_macro2::TokenStream::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        match (&tokens[i], tokens.get(i + 1)) {
+            (TokenTree::Punct(pound), Some(TokenTree::Group(group)))
+                if pound.as_char() == '#' && overload(group) =>
+            {
+                let attr = tokens[i..i + 2].iter().cloned().collect();
+                let mut attr = syn::Attribute::parse_outer
+                    .parse2(attr)?
+                    .pop()
+                    .expect("the tokens are one attribute");
+                complete_attr(&mut attr, &owner, &params)?;
+                completed.extend(attr.to_token_stream());
+                i += 2;
+            }
+            (token, _) => {
+                completed.extend([token.clone()]);
+                i += 1;
+            }
+        }
+    }
+    Ok(completed)
+}
+
+/// Completes an `overload` or `overload_set` attribute of a method of an impl marked with `overload_impl` with the
+/// `owner` of the impl and the `params` of a generic impl, unless the attribute already gives them.
+fn complete_attr(
+    attr: &mut syn::Attribute,
+    owner: &proc_macro2::TokenStream,
+    params: &[&proc_macro2::Ident],
+) -> syn::Result<()> {
+    let set = attr.path().is_ident("overload_set");
+    if !set && !attr.path().is_ident("overload") {
+        return Ok(());
+    }
+    let tokens = match &attr.meta {
+        syn::Meta::Path(_) => proc_macro2::TokenStream::new(),
+        syn::Meta::List(list) => list.tokens.clone(),
+        syn::Meta::NameValue(meta) => {
+            return Err(syn::Error::new_spanned(meta, "expected `#[overload(..)]`"));
+        }
+    };
+    let args = match set {
+        true => OverloadArgs::parse_set.parse2(tokens.clone())?,
+        false => syn::parse2::<OverloadArgs>(tokens.clone())?,
+    };
+    let mut parts = Vec::new();
+    if args.struct_name.is_none() {
+        parts.push(owner.clone());
+    }
+    if !args.generic && !params.is_empty() {
+        parts.push(quote! { generic(#(#params),*) });
+    }
+    if parts.is_empty() {
+        return Ok(());
+    }
+    // The macro name of `overload_set` stays first.
+    let mut tokens = tokens.into_iter().collect::<Vec<_>>();
+    let name = set.then(|| tokens.remove(0));
+    if name.is_some()
+        && matches!(tokens.first(), Some(proc_macro2::TokenTree::Punct(comma)) if comma.as_char() == ',')
+    {
+        tokens.remove(0);
+    }
+    if !tokens.is_empty() {
+        parts.push(tokens.into_iter().collect());
+    }
+    attr.meta = match name {
+        Some(name) => syn::parse_quote! { overload_set(#name, #(#parts),*) },
+        None => syn::parse_quote! { overload(#(#parts),*) },
+    };
+    Ok(())
+}
+
+/// Overload registered by an attribute whose arguments `parser` reads from `attr`, along with the overloads of the
+/// lower arities generated for `optional_from`, each parsed again from the same arguments.
+fn overload_items(
+    attr: TokenStream,
+    function: ItemFn,
+    content: u64,
+    parser: fn(syn::parse::ParseStream) -> syn::Result<OverloadArgs>,
+) -> TokenStream {
+    let args = match parser.parse(attr.clone()) {
+        Ok(args) => args,
+        Err(error) => return error.into_compile_error().into(),
+    };
+    let Some(from) = args.optional_from else {
+        return overload_item(args, function, content);
+    };
+    if args.trait_path.is_some() || args.by_len || args.by_literal || args.op.is_some() {
+        return syn::Error::new(
+            function.sig.ident.span(),
+            "`optional_from` adds functions of other arities, which methods of trait impls can't declare and \
+             `by_len`, `by_literal` or `op` wouldn't tell apart",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let receiver = matches!(function.sig.inputs.first(), Some(FnArg::Receiver(_)));
+    let arity = function.sig.inputs.len() - usize::from(args.skip_self && receiver);
+    let target = renamed(&args, mangling().as_ref(), &function.sig.ident, arity, "");
+    let delegates = match optional_delegates(&function, &target, from, args.struct_name.is_some()) {
+        Ok(delegates) => delegates,
+        Err(error) => return error.into_compile_error().into(),
+    };
+    let mut items = overload_item(args, function, content);
+    for delegate in delegates {
+        let mut args = parser
+            .parse(attr.clone())
+            .expect("the arguments parsed for the overload");
+        // The impls, the alias under the original name and the keyword call stay with the overload taking every
+        // parameter, whose defaults cover the delegates.
+        args.optional_from = None;
+        args.keywords = None;
+        args.default = false;
+        args.derive_default = false;
+        args.derive_from = false;
+        let n_args = delegate.sig.inputs.len() - usize::from(receiver);
+        args.places.retain(|&place| place < n_args);
+        let content = content_hash(&attr.to_string(), &delegate.to_token_stream().to_string());
+        items.extend(overload_item(args, delegate, content));
+    }
+    items
+}
+
+/// Name given to the overload `ident` of `arity` arguments, by the configured `scheme` or `{name}_{arity}`, followed
+/// by the `distinct` suffix of overloads sharing an arity.
+fn renamed(
+    args: &OverloadArgs,
+    scheme: Option<&Mangling>,
+    ident: &proc_macro2::Ident,
+    arity: usize,
+    distinct: &str,
+) -> proc_macro2::Ident {
+    // `define_overloads!` looks the overloads up by their default name.
+    let scheme = scheme.filter(|_| args.set.is_none() && args.trait_path.is_none());
+    let mangled = match scheme {
+        Some(scheme) => scheme.apply(&ident.unraw().to_string(), arity),
+        None => format!("{}_{}", ident.unraw(), arity),
+    };
+    // The macros name the overload from the site of `macros!()`, so the new name resolves there even when the
+    // function comes from a macro whose names are hygienic, keeping the location of the original for diagnostics.
+    let span = ident.span().resolved_at(proc_macro2::Span::call_site());
+    match &args.trait_path {
+        Some(_) => ident.clone(),
+        // A scheme starting with underscores doesn't add more after the prefix.
+        None if args.hidden_name => format_ident!(
+            "__overfn_{}{}",
+            match scheme {
+                Some(_) => mangled.trim_start_matches('_'),
+                None => &mangled,
+            },
+            distinct,
+            span = span
+        ),
+        None => format_ident!("{}{}", mangled, distinct, span = span),
+    }
+}
+
+/// Function item given to the `attribute`, either a free function or a method of an impl, with an error naming the
+/// kind of any other item.
+fn function_item(item: TokenStream, attribute: &str) -> syn::Result<ItemFn> {
+    if let Ok(UnparsedFn(function)) = syn::parse(item.clone()) {
+        return Ok(function);
+    }
+    let kind = match syn::parse::<syn::Item>(item.clone()) {
+        Ok(syn::Item::Fn(function)) => return Ok(function),
+        Ok(syn::Item::Verbatim(_)) | Err(_) => {
+            if let Ok(syn::TraitItemFn {
+                default: None, sig, ..
+            }) = syn::parse(item.clone())
+            {
+                return Err(syn::Error::new(
+                    sig.ident.span(),
+                    format!(
+                        "`#[{}]` only applies to trait methods with a default body",
+                        attribute
+                    ),
+                ));
+            }
+            let function = syn::parse::<syn::ImplItemFn>(item)?;
+            if let Some(default) = function.defaultness {
+                return Err(syn::Error::new(
+                    default.span,
+                    format!("`#[{}]` can't be applied to `default fn` items", attribute),
+                ));
+            }
+            return Ok(ItemFn {
+                attrs: function.attrs,
+                vis: function.vis,
+                sig: function.sig,
+                block: Box::new(function.block),
+            });
+        }
+        Ok(syn::Item::Struct(_)) => "a struct",
+        Ok(syn::Item::Enum(_)) => "an enum",
+        Ok(syn::Item::Union(_)) => "a union",
+        Ok(syn::Item::Const(_)) => "a const",
+        Ok(syn::Item::Static(_)) => "a static",
+        Ok(syn::Item::Trait(_) | syn::Item::TraitAlias(_)) => "a trait",
+        Ok(syn::Item::Impl(_)) => "an impl block",
+        Ok(syn::Item::Type(_)) => "a type alias",
+        Ok(syn::Item::Mod(_)) => "a module",
+        Ok(syn::Item::Use(_)) => "a `use` item",
+        Ok(syn::Item::Macro(_)) => "a macro",
+        Ok(_) => "this item",
+    };
+    Err(syn::Error::new(
+        proc_macro2::Span::call_site(),
+        format!(
+            "`#[{}]` may only be applied to fn items, not to {}; to overload the methods of an impl, apply \
+             `#[overload(Type)]` to each of them",
+            attribute, kind
+        ),
+    ))
+}
+
+/// Renames an overloaded function and registers it, shared by [`overload`](macro@overload) and
+/// [`overload_set`](macro@overload_set).
+fn overload_item(args: OverloadArgs, mut function: ItemFn, content: u64) -> TokenStream {
+    let ident = &function.sig.ident;
+    let n_args = &function.sig.inputs.len();
+    let receiver = matches!(function.sig.inputs.first(), Some(syn::FnArg::Receiver(_)));
+    let arity = match args.skip_self && receiver {
+        true => n_args - 1,
+        false => *n_args,
+    };
+    if args.by_len && (args.set.is_some() || args.into.is_some() || !args.places.is_empty()) {
+        return syn::Error::new(
+            ident.span(),
+            "`by_len` can't be combined with `set`, `into` or `place`",
+        )
+        .into_compile_error()
+        .into();
+    }
+    if args.by_literal
+        && (receiver
+            || *n_args == 0
+            || args.by_len
+            || args.wrap_unsafe
+            || args.set.is_some()
+            || args.into.is_some()
+            || !args.places.is_empty())
+    {
+        return syn::Error::new(
+            ident.span(),
+            "`by_literal` only applies to functions with parameters and without receiver, \
+             and can't be combined with `set`, `into`, `place`, `by_len` or `wrap_unsafe`",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let array_len = match args.by_len {
+        true => match array_len(&function) {
+            Ok(len) => Some(len),
+            Err(error) => return error.into_compile_error().into(),
+        },
+        false => None,
+    };
+    // Methods of trait impls must keep the name declared by the trait.
+    if args.hidden_name && (args.trait_path.is_some() || args.set.is_some()) {
+        return syn::Error::new(
+            ident.span(),
+            "`mangle = hidden` can't rename methods of trait impls or overloads found by `define_overloads!`",
+        )
+        .into_compile_error()
+        .into();
+    }
+    // Overloads sharing an arity are told apart by the length of the array or the types of the arguments.
+    let distinct = match array_len {
+        Some(len) => format!("_{}", len),
+        None if args.by_literal || args.op.is_some() => {
+            let mut words = Vec::new();
+            for input in &function.sig.inputs {
+                if let FnArg::Typed(arg) = input {
+                    type_words(arg.ty.to_token_stream(), &mut words);
+                }
+            }
+            format!("_{}", words.join("_"))
+        }
+        None => String::new(),
+    };
+    // `define_overloads!` looks the overloads up by their default name.
+    let scheme = mangling().filter(|_| args.set.is_none() && args.trait_path.is_none());
+    let inline = inlined();
+    let new_ident = renamed(&args, scheme.as_ref(), ident, arity, &distinct);
+
+    // The trait stands in for the type, which the macro leaves to the receiver, so the items needing a type are out.
+    if args.trait_def
+        && (!receiver
+            || args.struct_name.is_none()
+            || args.type_args.is_some()
+            || args.trait_path.is_some()
+            || !args.impl_params.is_empty()
+            || args.set.is_some()
+            || args.chain
+            || args.catch_unwind
+            || args.default
+            || args.extern_name.is_some()
+            || args.ext
+            || args.inherent
+            || args.method_call)
+    {
+        return syn::Error::new(
+            ident.span(),
+            "`trait_def` only applies to methods taking a receiver in the trait named by the attribute, \
+             `#[overload(Trait, trait_def)]`, and can't be combined with generic arguments, `as`, `generic(..)`, \
+             `set`, `chain`, `catch_unwind`, `default`, `extern_name`, `ext`, `inherent` or `receiver = method`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    // Without its type, a method would be called as a free function and named like the free functions of its
+    // arity, silently joining their set.
+    if receiver && args.struct_name.is_none() {
+        let message = format!(
+            "`{}` takes a receiver, so it needs the type of its impl: `#[overload(Type)]`",
+            ident
+        );
+        return syn::Error::new(ident.span(), message)
+            .into_compile_error()
+            .into();
+    }
+    if let (Some(type_args), false) = (&args.type_args, args.impl_params.is_empty()) {
+        return syn::Error::new_spanned(
+            type_args,
+            "`generic(..)` leaves the parameters of the type to inference, it can't be combined with generic arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let (arg_type, macro_ident) = match (&args.struct_name, &args.trait_path) {
+        // Sets are named without the `r#` of raw identifiers, which the generated macro gets back for keywords.
+        (None, _) => (ArgType::Other, ident.unraw().to_string()),
+        (Some(struct_name), Some(trait_path)) => (
+            ArgType::Trait(
+                struct_name.to_string(),
+                trait_path.to_token_stream().to_string(),
+            ),
+            format!("{}_{}", struct_name.unraw(), ident.unraw()),
+        ),
+        (Some(struct_name), None) => {
+            let arg_type = match receiver {
+                true => ArgType::Instance(struct_name.to_string()),
+                false => ArgType::Struct(struct_name.to_string()),
+            };
+            (
+                arg_type,
+                format!("{}_{}", struct_name.unraw(), ident.unraw()),
+            )
+        }
+    };
+    let macro_ident = match &args.macro_name {
+        Some(name) if args.set.is_some() => {
+            return syn::Error::new(
+                name.span(),
+                "`overload_set` names the macro of `macros!()`, it can't be combined with `set`",
+            )
+            .into_compile_error()
+            .into();
+        }
+        Some(name) => name.unraw().to_string(),
+        None => macro_ident,
+    };
+
+    if let Some(defaults) = &args.keywords {
+        // Parameters taking a pattern are named by their position, as in the documentation.
+        let params = param_names(&function);
+        let error = match &params {
+            _ if receiver || args.set.is_some() => Some((
+                ident.span(),
+                "`keywords` only applies to functions without receiver registered for `macros!()`"
+                    .to_owned(),
+            )),
+            params => defaults.iter().enumerate().find_map(|(i, (name, _))| {
+                let message = match defaults[..i].iter().any(|(other, _)| other == name) {
+                    true => format!("`{}` is given a default twice", name),
+                    false if !params.iter().any(|param| name == param) => {
+                        format!("`{}` isn't a parameter of `{}`", name, ident)
+                    }
+                    false => return None,
+                };
+                Some((name.span(), message))
+            }),
+        };
+        if let Some((span, message)) = error {
+            return syn::Error::new(span, message).into_compile_error().into();
+        }
+    }
+
+    if args.copy_receiver && (!receiver || args.chain || args.set.is_some()) {
+        let message = match (receiver, args.chain) {
+            (false, _) => "`receiver = copy` needs a method taking `self`, `&self` or `&mut self`",
+            (true, true) => {
+                "`receiver = copy` can't be combined with `chain`, which would return the copy"
+            }
+            (true, false) => "`receiver = copy` isn't supported for stateless sets",
+        };
+        return syn::Error::new(ident.span(), message)
+            .into_compile_error()
+            .into();
+    }
+
+    // The receiver of a method is passed first, as to its macro, and counts unless the set skips it. The siblings
+    // called are recorded for `audit!()`, which checks that they were registered.
+    let siblings = std::cell::RefCell::new(Vec::new());
+    let callee = |n_args: usize, span: proc_macro2::Span| match &args.trait_path {
+        Some(_) => Err(syn::Error::new(
+            span,
+            "`self_overload!` can't call other overloads from methods of trait impls, \
+             which keep their name",
+        )),
+        None => {
+            let arity = n_args.saturating_sub(usize::from(args.skip_self && receiver));
+            let sibling = renamed(&args, scheme.as_ref(), ident, arity, "");
+            siblings.borrow_mut().push(sibling.to_string());
+            Ok(match args.struct_name {
+                Some(_) => quote! { Self::#sibling },
+                None => quote! { #sibling },
+            })
+        }
+    };
+    let stmts = &function.block.stmts;
+    let body = match self_calls(quote! { #(#stmts)* }, &callee) {
+        Ok(body) => body,
+        Err(error) => return error.into_compile_error().into(),
+    };
+    function.block.stmts = vec![syn::Stmt::Item(syn::Item::Verbatim(body))];
+
+    if args.method_call && (!receiver || args.trait_path.is_some() || args.set.is_some()) {
+        return syn::Error::new(
+            ident.span(),
+            "`receiver = method` only applies to methods of inherent impls registered for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let params = function
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(arg) => Some(&*arg.ty),
+            FnArg::Receiver(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let misplaced = args.places.iter().find(|&&place| {
+        !matches!(params.get(place), Some(Type::Reference(reference)) if reference.mutability.is_some())
+    });
+    if let Some(place) = misplaced {
+        return syn::Error::new(
+            ident.span(),
+            format!(
+                "`place({})` needs the parameter at that position to be a `&mut` reference",
+                place
+            ),
+        )
+        .into_compile_error()
+        .into();
+    }
+    if !args.places.is_empty()
+        && (args.set.is_some()
+            || args
+                .into
+                .as_ref()
+                .is_some_and(|arities| !arities.is_empty()))
+    {
+        return syn::Error::new(
+            ident.span(),
+            "`place` only applies to overloads registered for `macros!()` calling the function with its own arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if (args.pyo3 || args.wasm) && (args.struct_name.is_some() || args.set.is_some()) {
+        return syn::Error::new(
+            ident.span(),
+            "`pyo3` and `wasm` only apply to free functions registered for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if let Some(module) = args.module.as_ref().filter(|_| args.set.is_some()) {
+        return syn::Error::new_spanned(
+            module,
+            "`module` only applies to overloads registered for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if args.ctor && (!matches!(arg_type, ArgType::Struct(_)) || args.set.is_some()) {
+        return syn::Error::new(
+            ident.span(),
+            "`ctor` only applies to associated functions without receiver registered for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if args.derive_default
+        && (!matches!(arg_type, ArgType::Struct(_))
+            || *n_args != 0
+            || args.generic
+            || !function.sig.generics.params.is_empty()
+            || function.sig.unsafety.is_some()
+            || args.set.is_some())
+    {
+        return syn::Error::new(
+            ident.span(),
+            "`derive_default` only applies to safe associated functions without arguments or generics registered \
+             for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if args.derive_from
+        && (!matches!(arg_type, ArgType::Struct(_))
+            || *n_args != 1
+            || args.generic
+            || !function.sig.generics.params.is_empty()
+            || function.sig.unsafety.is_some()
+            || args.set.is_some())
+    {
+        return syn::Error::new(
+            ident.span(),
+            "`derive_from` only applies to safe associated functions with one argument and no generics registered \
+             for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if let Some(module) = args.module.as_ref().filter(|_| args.derive_from) {
+        return syn::Error::new_spanned(
+            module,
+            "`derive_from` can't be combined with `module`: the argument type is written for the scope of the module",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if let Some(op) = args.op.as_ref().filter(|_| {
+        arg_type != ArgType::Other
+            || *n_args != 2
+            || args.generic
+            || !function.sig.generics.params.is_empty()
+            || function.sig.unsafety.is_some()
+            || matches!(function.sig.output, syn::ReturnType::Default)
+            || args.module.is_some()
+            || args.set.is_some()
+            || args.by_literal
+            || args.into.is_some()
+            || !args.places.is_empty()
+    }) {
+        return syn::Error::new_spanned(
+            op,
+            "`op` only applies to safe free functions with two arguments, a return type and no generics registered \
+             for `macros!()`, and can't be combined with `module`, `by_literal`, `into` or `place`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if (args.ext || args.inherent)
+        && (!matches!(arg_type, ArgType::Instance(_)) || args.set.is_some())
+    {
+        return syn::Error::new(
+            ident.span(),
+            "`ext` and `inherent` only apply to methods of inherent impls registered for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if let (Some(alias), Some(_)) = (args.aliases.first(), &args.set) {
+        return syn::Error::new(
+            alias.span(),
+            "`alias` only applies to overloads registered for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let unsafety = function.sig.unsafety.is_some();
+    if args.wrap_unsafe && (!unsafety || args.set.is_some()) {
+        return syn::Error::new(
+            ident.span(),
+            "`wrap_unsafe` only applies to unsafe functions registered for `macros!()`",
+        )
+        .into_compile_error()
+        .into();
+    }
+    if unsafety
+        && (args.ext
+            || args.inherent
+            || args.dispatch
+            || args.table
+            || args.pyo3
+            || args.wasm
+            || args.by_len)
+    {
+        return syn::Error::new(
+            ident.span(),
+            "`ext`, `inherent`, `dispatch`, `table`, `pyo3`, `wasm` and `by_len` generate safe items calling the \
+             overload, which can't be unsafe",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    if args.no_macro
+        && (args.set.is_some()
+            || args.export
+            || args.ctor
+            || args.dispatch
+            || args.table
+            || args.pyo3
+            || args.wasm
+            || args.ext
+            || args.inherent
+            || args.derive_default
+            || args.derive_from
+            || args.op.is_some()
+            || args.unify_return.is_some()
+            || args.keywords.is_some())
+    {
+        return syn::Error::new(
+            ident.span(),
+            "`no_macro` leaves the overload out of the items `macros!()` generates for its set, so it can't be \
+             combined with `set`, `export`, `ctor`, `dispatch`, `table`, `pyo3`, `wasm`, `ext`, `inherent`, \
+             `derive_default`, `derive_from`, `op`, `unify_return` or `keywords`",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let wrapper = match (args.chain, args.catch_unwind, &args.set, &arg_type) {
+        (false, false, _, _) => None,
+        (true, true, _, _) => {
+            return syn::Error::new(ident.span(), "`chain` and `catch_unwind` can't be combined")
+                .into_compile_error()
+                .into();
+        }
+        (true, _, None, ArgType::Instance(_)) => Some(chain_wrapper(&function, &new_ident)),
+        (true, _, _, _) => {
+            return syn::Error::new(
+                ident.span(),
+                "`chain` only applies to methods registered for `macros!()`, such as `#[overload(Type, chain)]`",
+            )
+            .into_compile_error()
+            .into();
+        }
+        (_, true, None, ArgType::Other | ArgType::Struct(_) | ArgType::Instance(_)) => Some(
+            catch_unwind_wrapper(&function, &new_ident, args.struct_name.is_some()),
+        ),
+        (_, true, _, _) => {
+            return syn::Error::new(
+                ident.span(),
+                "`catch_unwind` only applies to overloads registered for `macros!()` outside of trait impls",
+            )
+            .into_compile_error()
+            .into();
+        }
+    };
+    let mut wrapper = match wrapper.transpose() {
+        Ok(wrapper) => wrapper,
+        Err(error) => return error.into_compile_error().into(),
+    };
+    if args.default
+        && (args.trait_path.is_some() || (args.set.is_some() && args.struct_name.is_none()))
+    {
+        let message = match args.trait_path {
+            Some(_) => "methods of trait impls keep their name, `default` isn't needed",
+            None => "`default` would clash with the macro that `define_overloads!` re-exports under the same name",
+        };
+        return syn::Error::new(ident.span(), message)
+            .into_compile_error()
+            .into();
+    }
+    let alias = args
+        .default
+        .then(|| default_alias(&function, &new_ident, args.struct_name.is_some()))
+        .transpose();
+    let mut alias = match alias {
+        Ok(alias) => alias,
+        Err(error) => return error.into_compile_error().into(),
+    };
+
+    let shim = match (&args.extern_name, &arg_type) {
+        (None, _) => None,
+        (Some(name), ArgType::Trait(..)) => {
+            return syn::Error::new(
+                name.span(),
+                "`extern_name` can't export methods of trait impls, which can't hold other items",
+            )
+            .into_compile_error()
+            .into();
+        }
+        (Some(name), _) => {
+            // The exported names follow the default renaming, `mylib_test_2` for `test_2`, whatever the mangling.
+            let shim = format_ident!("{}_{}{}", name, arity, distinct, span = name.span());
+            let shim = extern_shim(&function, &new_ident, &shim, args.struct_name.is_some());
+            match shim {
+                Ok(shim) => Some(shim),
+                Err(error) => return error.into_compile_error().into(),
+            }
+        }
+    };
+
+    let marker = match (&args.set, &arg_type) {
+        (Some(_), ArgType::Trait(..)) => {
+            return syn::Error::new(
+                ident.span(),
+                "`set` can't be used on methods of trait impls, which keep their name",
+            )
+            .into_compile_error()
+            .into();
+        }
+        (Some(set), ArgType::Other) => {
+            set_marker(set.as_ref().unwrap_or(ident), &new_ident, *n_args)
+        }
+        (Some(Some(set)), _) => {
+            return syn::Error::new(
+                set.span(),
+                "set names only apply to free functions; methods are collected with \
+                 `define_overloads!(Type::name)` or `define_overloads!(Type.name)`",
+            )
+            .into_compile_error()
+            .into();
+        }
+        (Some(None), _) => quote! {},
+        (None, _) => {
+            if let Some(vis) = least_visible(&macro_ident).filter(|_| args.inherit_vis) {
+                function.vis = vis;
+            }
+            let mut data = match &wrapper {
+                Some(wrapper) => {
+                    FunctionData::new(wrapper.sig.ident.to_string(), arg_type, wrapper)
+                }
+                None => FunctionData::new(new_ident.to_string(), arg_type, &function),
+            };
+            data.export = args.export;
+            data.local = args.local;
+            data.no_macro = args.no_macro;
+            data.pyo3 = args.pyo3;
+            data.wasm = args.wasm;
+            data.alias = args.default;
+            data.ctor = args.ctor;
+            data.module = args
+                .module
+                .as_ref()
+                .map(|module| module.to_token_stream().to_string());
+            data.derive_default = args.derive_default;
+            data.derive_from = args.derive_from;
+            data.op = args.op.as_ref().map(ToString::to_string);
+            data.unify_return = args.unify_return.as_ref().map(ToString::to_string);
+            data.inline = inline;
+            data.dispatch = args.dispatch;
+            data.table = args.table;
+            data.internal = args.internal;
+            data.priority = args.priority;
+            data.copy_receiver = args.copy_receiver;
+            data.method_call = args.method_call;
+            data.ext = args.ext;
+            data.inherent = args.inherent;
+            if args.wrap_unsafe {
+                data.safety = Safety::Wrapped;
+            }
+            data.places = args.places.clone();
+            data.array_len = array_len;
+            data.by_literal = args.by_literal;
+            data.keywords = args.keywords.as_ref().map(|defaults| {
+                defaults
+                    .iter()
+                    .map(|(name, default)| {
+                        (name.to_string(), default.to_token_stream().to_string())
+                    })
+                    .collect()
+            });
+            data.grouped = args.macro_name.is_some();
+            // Methods of a trait definition are generic over the type implementing the trait.
+            data.generic |= args.generic || args.trait_def;
+            data.trait_def = args.trait_def;
+            data.impl_params = args.impl_params.len();
+            data.type_args = args
+                .type_args
+                .as_ref()
+                .map(|type_args| type_args.to_token_stream().to_string());
+            data.content = content;
+            data.self_calls = siblings.take();
+            let adapters = match &args.into {
+                Some(arities) if !arities.is_empty() && data.arg_types.len() != 1 => {
+                    return syn::Error::new(
+                        ident.span(),
+                        "`into` arities pack the arguments into the single parameter of the function, \
+                         which must take exactly one argument",
+                    )
+                    .into_compile_error()
+                    .into();
+                }
+                Some(arities) => {
+                    data.convert = Convert::Into;
+                    arities
+                        .iter()
+                        .map(|arity| FunctionData {
+                            n_args: arity + usize::from(receiver),
+                            convert: Convert::Pack,
+                            ..data.clone()
+                        })
+                        .collect()
+                }
+                None => Vec::new(),
+            };
+            // Each alias set takes a copy of the overload, grouped like the sets of `overload_set`, and checks it
+            // for duplicates among its own overloads.
+            let members = std::iter::once(data).chain(adapters).collect::<Vec<_>>();
+            let aliases = args.aliases.iter().map(|alias| (alias.to_string(), true));
+            let duplicates = std::iter::once((macro_ident.clone(), false))
+                .chain(aliases)
+                .flat_map(|(set, alias)| {
+                    members.iter().map(move |data| {
+                        let data = FunctionData {
+                            grouped: data.grouped || alias,
+                            ..data.clone()
+                        };
+                        (set.clone(), data)
+                    })
+                })
+                .filter_map(|(set, data)| register(ident, set, data, args.replace).err())
+                .map(syn::Error::into_compile_error)
+                .collect::<Vec<_>>();
+            quote! { #(#duplicates)* }
+        }
+    };
+
+    let macro_ident = match &args.set {
+        Some(Some(set)) => set.to_string(),
+        _ => macro_ident,
+    };
+    let public = matches!(function.vis, syn::Visibility::Public(_));
+    if args.trait_path.is_none() {
+        function
+            .attrs
+            .extend(family_docs(ident, *n_args, &macro_ident, args.doc, public));
+    }
+    // Constructors named after enum variants (`fn Circle(radius: f64) -> Self`) keep their casing in the new names,
+    // and the names of a configured scheme are the crate's choice.
+    let variant = args.struct_name.is_some()
+        && !receiver
+        && ident.to_string().starts_with(char::is_uppercase);
+    function.sig.ident = new_ident;
+    if inline {
+        for item in std::iter::once(&mut function).chain(&mut wrapper) {
+            item.attrs.retain(|attr| !attr.path().is_ident("inline"));
+            item.attrs.push(syn::parse_quote! { #[inline(always)] });
+        }
+    }
+    if variant || scheme.is_some() {
+        let items = std::iter::once(&mut function)
+            .chain(&mut wrapper)
+            .chain(&mut alias);
+        for item in items {
+            item.attrs
+                .push(syn::parse_quote! { #[allow(non_snake_case)] });
+        }
+    }
+
+    quote! {
+        #function
+        #wrapper
+        #alias
+        #shim
+        #marker
+    }
+    .into()
+}
+
+/// Body of an overload with every `self_overload!(..)` replaced by a call of the overload of its set taking as many
+/// arguments, named by `callee` from the number of arguments and the span of the invocation.
+fn self_calls(
+    tokens: proc_macro2::TokenStream,
+    callee: &dyn Fn(usize, proc_macro2::Span) -> syn::Result<proc_macro2::TokenStream>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    use proc_macro2::{Delimiter, Group, TokenTree};
+
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut body = proc_macro2::TokenStream::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i..] {
+            [TokenTree::Ident(name), TokenTree::Punct(bang), TokenTree::Group(call), ..]
+                if name == "self_overload" && bang.as_char() == '!' =>
+            {
+                let args = Punctuated::<Expr, Token![,]>::parse_terminated.parse2(call.stream())?;
+                body.extend(callee(args.len(), name.span())?);
+                let mut call =
+                    Group::new(Delimiter::Parenthesis, self_calls(call.stream(), callee)?);
+                call.set_span(name.span());
+                body.extend([TokenTree::Group(call)]);
+                i += 3;
+                continue;
+            }
+            [TokenTree::Group(group), ..] => {
+                let mut inner = Group::new(group.delimiter(), self_calls(group.stream(), callee)?);
+                inner.set_span(group.span());
+                body.extend([TokenTree::Group(inner)]);
+            }
+            [token, ..] => body.extend([token.clone()]),
+            [] => unreachable!("the index is within the tokens"),
+        }
+        i += 1;
+    }
+    Ok(body)
+}
+
+/// Length of the single array parameter of a `by_len` overload, which must be a literal to name the renamed function.
+fn array_len(function: &ItemFn) -> syn::Result<usize> {
+    let mut arrays = function.sig.inputs.iter().filter_map(|input| match input {
+        FnArg::Typed(arg) => match &*arg.ty {
+            Type::Array(array) => Some(array),
+            _ => None,
+        },
+        FnArg::Receiver(_) => None,
+    });
+    let ident = &function.sig.ident;
+    let (Some(array), None) = (arrays.next(), arrays.next()) else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`by_len` needs exactly one parameter taking an array",
+        ));
+    };
+    if function.sig.receiver().is_some() || !function.sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`by_len` only applies to functions without receiver or generic parameters",
+        ));
+    }
+    match &array.len {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(len),
+            ..
+        }) => len.base10_parse(),
+        len => Err(syn::Error::new_spanned(
+            len,
+            "`by_len` needs the length of the array as an integer literal",
+        )),
+    }
+}
+
+/// Identifiers, lowercased, and integer literals of the parameter type of a `by_literal` or `op` overload, without
+/// lifetimes, naming the renamed function after its types (`parse_1_str` for `fn parse(value: &str)`).
+fn type_words(tokens: proc_macro2::TokenStream, words: &mut Vec<String>) {
+    let mut lifetime = false;
+    for token in tokens {
+        match &token {
+            proc_macro2::TokenTree::Ident(ident) if !lifetime => {
+                words.push(ident.to_string().to_lowercase())
+            }
+            proc_macro2::TokenTree::Literal(literal) => words.push(literal.to_string()),
+            proc_macro2::TokenTree::Group(group) => type_words(group.stream(), words),
+            _ => {}
+        }
+        lifetime =
+            matches!(&token, proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'');
+    }
+}
+
+/// Generate the macros to call the overloaded functions. You need to call this macro after all the functions are
+/// overloaded.
+///
+/// For more information, see the [`overload`](macro@overload) macro.
+///
+/// # Scope
+///
+/// Besides being defined with `macro_rules!`, every generated macro is re-exported by path (`pub(crate) use add;`).
+/// This makes it usable anywhere in the module that invokes `macros!()`, including code written above the
+/// invocation, and reachable from other modules through its path (`crate::add!` when `macros!()` is at the crate
+/// root). When a call still can't see the macro, the compiler suggests the import pointing at the `macros!()` site.
+/// Overloads can therefore call their own set and each other's (`fn even(n: u32)` calling `odd!(n - 1)` and the other
+/// way around) without any forward declaration.
+///
+/// Each macro is generated once per module: overloads of a set registered after the `macros!()` that generated it can't
+/// be added to it by a later `macros!()` of the same module, which fails naming the first one instead of defining a
+/// second macro of the same name. Place the `macros!()` below every overload of its sets, or give the later overloads a
+/// set of their own with [`overload_set`](macro@overload_set) or `module = path`. Sets of the same name declared in
+/// other modules, such as a `#[cfg(test)] mod tests`, each get their own macro from the `macros!()` of their module,
+/// and local sets, scoped to their block, are left out of this.
+///
+/// # Lints
+///
+/// The generated macros and their re-exports are marked `#[allow(unused_macros)]` and `#[allow(unused_imports)]`, so
+/// the sets a module doesn't call don't warn. `macros!(lints(deny(unused_macros), warn(unused_imports)))` puts the
+/// given lint attributes (`allow`, `expect`, `warn`, `deny` or `forbid`) on both instead, for the sets of the
+/// invocation.
+///
+/// # Order
+///
+/// The macros of an invocation are generated sorted by name, whatever the order of the overloads, so the expanded
+/// code only changes with the sets. `macros!(order = registration)` generates them in the order their first overloads
+/// were registered instead, so adding a set to the end of a file only adds its macro to the end of the expansion when
+/// diffing it between commits. `order = name` is the default.
+///
+/// # Selecting sets
+///
+/// `macros!(only(add, Test_new))` generates only the named macros and leaves every other set in the registry for a
+/// later `macros!()`, so a module can generate the macros it uses without waiting for the whole crate.
+/// `macros!(except(add))` does the opposite and keeps just the named sets for later.
+///
+/// # Sets by module
+///
+/// Overloads declared with `module = path` don't clash with overloads of the same name and arity declared in other
+/// modules, so several modules can each define their own `decode`. `macros!(module = json)` generates only the
+/// overloads declared with `module = json`, under macro names prefixed with the path of the module (`json_decode!`,
+/// or `formats_json_decode!` for `module = formats::json`), and leaves the overloads of the other modules for their
+/// own `macros!()`. A plain `macros!()` still generates every module into one macro, and fails when the overloads of
+/// different modules take the same arguments.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// mod json {
+///     #[overfn::overload(module = json)]
+///     pub fn decode(text: &str) -> Vec<&str> {
+///         text.trim_matches(['[', ']']).split(',').collect()
+///     }
+/// }
+///
+/// mod csv {
+///     #[overfn::overload(module = csv)]
+///     pub fn decode(text: &str) -> Vec<&str> {
+///         text.split(';').collect()
+///     }
+/// }
+///
+/// macros!(module = json);
+/// macros!(module = csv);
+///
+/// # fn main() {
+/// assert_eq!(json_decode!("[a,b]"), ["a", "b"]);
+/// assert_eq!(csv_decode!("a;b"), ["a", "b"]);
+/// # }
+/// ```
+///
+/// # Shadowing the standard macros
+///
+/// A set named like a macro of the standard library prelude (`println`, `format`, `vec`, ...) would shadow it for the
+/// rest of the module, so `macros!()` rejects it. Name the macro of the set with
+/// [`overload_set`](macro@overload_set), or prefix every macro generated by the invocation with
+/// `macros!(prefix = "ov_")`, which generates `ov_format!` for the `format` overloads. The prefix goes before any
+/// filter: `macros!(prefix = "ov_", only(format))`.
+///
+/// # Local sets
+///
+/// Functions declared inside a function body can be overloaded too, but the `macros!()` in the body would expand in
+/// source order and also take the sets of the module still waiting for their own `macros!()`. Mark the local overloads
+/// with `#[overload(local)]` and generate them with `macros!(local)` (or `macros!(local, only(..))`) in statement
+/// position: it generates only the sets with local overloads, which a plain `macros!()` leaves alone, and the macros
+/// are scoped to the block.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///     10 + item
+/// }
+///
+/// fn total() -> usize {
+///     #[overload(local)]
+///     fn double(item: usize) -> usize {
+///         item * 2
+///     }
+///
+///     #[overload(local)]
+///     fn double(left: usize, right: usize) -> usize {
+///         (left + right) * 2
+///     }
+///
+///     macros!(local);
+///
+///     double!(1) + double!(1, 2)
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(total(), 8);
+/// assert_eq!(add!(1), 11);
+/// ```
+///
+/// # Report
+///
+/// Set the `OVERFN_REPORT` environment variable to a directory to have every `macros!()` write a Markdown summary of
+/// the sets generated so far in the crate to `mycrate.md` in it, with the arguments, receiver and definition site of
+/// each overload. The last invocation leaves the report of the whole crate. Integration tests, examples and doctests
+/// are crates of their own and write their own file, while the library and its unit tests share its name, so the last
+/// one built is the one kept. The compiler doesn't track the variable, so an already built crate has to be rebuilt
+/// (`cargo clean -p mycrate`) for the report to be written.
+///
+/// # Integration tests, benches and doctests
+///
+/// Integration tests, benches, examples and doctests are separate crates compiled on their own, so they can't see the
+/// overloads registered by the library. Call `macros!(persist)` in the library to also save the sets it generates,
+/// keyed by crate and source file, in the `OUT_DIR` of the package, and `macros!(import = "mycrate")` in the other
+/// targets of the package to generate them again, calling the overloads through `::mycrate`. As with exported macros,
+/// only the `pub` overloads that aren't `internal` are imported, and they must be reachable from the crate root or
+/// declare their `module`. Cargo only sets `OUT_DIR` for packages with a build script, so the package needs a
+/// `build.rs`, which can be empty (`fn main() {}`). `import` accepts `prefix` and a filter, but not `local` or
+/// `persist`. Saved files are rewritten on every build of the library, but the file of a source file that no longer
+/// calls `macros!(persist)` stays until `cargo clean`.
+///
+/// ```rust,ignore
+/// // src/lib.rs
+/// #[overload]
+/// pub fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!(persist);
+///
+/// // tests/add.rs
+/// overfn::macros!(import = "mycrate");
+///
+/// #[test]
+/// fn test_add() {
+///     assert_eq!(add!(1, 2), 3);
+/// }
+/// ```
+///
+/// Without a build script, `macros!(persist)` fails to compile, and so does `macros!(import = "mycrate")`:
+///
+/// ```compile_fail
+/// use overfn::*;
+///
+/// #[overload]
+/// pub fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!(persist);
+/// # fn main() {}
+/// ```
+///
+/// # Documentation page
+///
+/// The renamed overloads are hidden from rustdoc, or listed one by one under their mangled names with `doc`.
+/// `macros!(docs)` also generates a module named `overloads`, or the module given with `docs = "name"`, that only
+/// exists when building the documentation, with a function named after each generated set listing the signature of
+/// every overload along with the first line of its doc comment:
+///
+/// ```text
+/// Overloads of the `add!` macro, selected by the arguments of the call:
+///
+/// - `add!(item: usize) -> usize`, calling `add_1`: Adds 10 to the item.
+/// - `add!(left: usize, right: usize) -> usize`, calling `add_2`
+/// ```
+///
+/// The functions take no arguments and can't be called, as the module is left out of every other build.
+///
+/// # Prelude
+///
+/// `macros!(prelude)` also generates a module named `overfn_prelude`, or the module given with `prelude = "name"`,
+/// re-exporting the `pub` free functions of the generated sets under their new names along with the
+/// [exported](macro@overload#exporting) macros, so other crates bring all of them into scope with
+/// `use mylib::overfn_prelude::*;`. The functions are re-exported from the module of the `macros!()`, or from the
+/// module given with `module = path`. Methods and associated functions stay with their type, and sets that aren't
+/// exported only keep their functions.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(export)]
+/// pub fn shout(word: &str) -> String {
+///     word.to_uppercase()
+/// }
+///
+/// #[overload]
+/// pub fn shout(word: &str, times: usize) -> String {
+///     word.to_uppercase().repeat(times)
+/// }
+///
+/// macros!(prelude);
+///
+/// # fn main() {
+/// assert_eq!(overfn_prelude::shout!("a", 2), "AA");
+/// assert_eq!(overfn_prelude::shout_1("b"), "B");
+/// # }
+/// ```
+///
+/// # IDEs and expansion tools
+///
+/// Tools such as rust-analyzer expand the attributes and `macros!()` again, in any order and sometimes on their own.
+/// An overload registered again unchanged is ignored, one edited in place replaces its earlier version, and a
+/// `macros!()` expanded again generates the same sets as before along with any new overload. A `macros!()` that can't
+/// see any overload of the crate still expands, with a warning instead of an error, and so does one left with nothing
+/// to generate, which usually comes before the overloads it was meant for.
+///
+/// # Compile times
+///
+/// The attributes only parse the signature of the overloads and pass their bodies through as tokens, so long function
+/// bodies add little to their cost. Likewise, [`#[overload_impl]`](macro@overload_impl) parses the header of the impl
+/// and the overload attributes of its methods, leaving the rest of the methods as tokens. Most of the time of
+/// `macros!()` goes into building the options of every set, which Cargo compiles without optimizations in the `dev`
+/// profile like any proc-macro. Crates with thousands of overloads expand noticeably faster with the macros optimized:
+///
+/// ```toml
+/// [profile.dev.build-override]
+/// opt-level = 3
+/// ```
+///
+/// # Spreading arguments
+///
+/// Prefixing a single argument with `..` spreads a tuple or an array into positional arguments and selects the
+/// overload by its length: `add!(..[1, 2])` and `add!(..(1, 2))` expand to `add!(1, 2)`. Besides literals, any tuple or
+/// array expression can be spread (`add!(..pair)`) when the overload selected by its type has a signature that can be
+/// named outside of it, without generics, `impl Trait` or borrowed return types. Instance methods spread the arguments
+/// after the receiver: `Test_test!(test, ..[2])`.
+///
+/// Any tuple can also be splatted by writing its length: `add!(@splat(2) pair)` expands to `add!(pair.0, pair.1)`,
+/// evaluating `pair` once, and works for every overload since no trait has to be implemented for the tuple.
+/// Instance methods splat the tuple after the receiver, `Test_test!(test, @splat(1) args)`, evaluating the tuple
+/// first.
+///
+/// # Partial application
+///
+/// Writing `_` in place of some arguments turns the call into a closure taking the missing arguments in order:
+/// `add!(10, _)` expands to `|arg_1| add!(10, arg_1)`, which can be passed to iterator adapters
+/// (`values.map(add!(10, _))`) and callbacks, and `Test_test!(_, 2)` leaves the receiver out, typed as the `self` of the
+/// method unless the impl is generic. The given arguments are evaluated on every call of the closure. Any combination
+/// of placeholders works for arities up to 4.
+///
+/// # Arity introspection
+///
+/// Every macro also answers which numbers of arguments it takes, counting the receiver of methods:
+/// `add!(@arity 2)` expands to `true` or `false`, for a literal written without suffix, and `add!(@arities)` to an
+/// array of the arities in increasing order (`[1, 2]`). Both are constant expressions, for build-time checks
+/// (`const _: () = assert!(add!(@arity 2));`) and for code generated on top of the sets.
+///
+/// # Callable structs
+///
+/// With the `fn_traits` feature, every set without receivers also gets a unit struct named after it implementing
+/// `Fn`, `FnMut` and `FnOnce` for the argument tuple of each overload with a nameable signature, so `add(1, 2)` works
+/// with the call syntax and `add` can be passed as a value (`[1, 2].map(add)`). The trait impls need the nightly
+/// `fn_traits` and `unboxed_closures` features in the crate calling `macros!()`:
+/// `#![feature(fn_traits, unboxed_closures)]`.
+///
+/// # Runtime dispatchers
+///
+/// With the `runtime` feature, every set without receivers also gets a dispatcher for calling it by name from a
+/// scripting host: `dispatch_add(values)`, generic over the type of the dynamic values of the host, takes the arguments
+/// as a `Vec` of values, or any other iterator of known length such as an array, picks the overload taking that many,
+/// converts each value to the type of its argument with `TryFrom` and the result back to a value with `From`. The
+/// error, `AddDispatchError`, is `UnknownArity { given, available }` when no overload takes `given` arguments, listing
+/// the arities the set takes, and `TypeMismatch { index }` when the value at `index` doesn't convert. It implements
+/// `Display` and `Error`, so hosts can report it as is. Overloads whose signature can't be named outside of them, or
+/// borrows, are left out, and the items have the visibility of the least visible overload.
+///
+/// # Example
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///    10 + item
+/// }
+///
+/// #[overload]
+/// fn add(left: usize, right: usize) -> usize {
+///   left + right
+/// }
+///
+/// macros!();
+///
+/// assert_eq!(add!(2), 12);
+/// assert_eq!(add!(2, 2), 4);
+/// ```
+#[proc_macro]
+pub fn macros(item: TokenStream) -> TokenStream {
+    let filter = parse_macro_input!(item as MacrosArgs);
+    if let Some(krate) = &filter.import {
+        return import(krate, &filter)
+            .unwrap_or_else(syn::Error::into_compile_error)
+            .into();
+    }
+    let module = filter
+        .module
+        .as_ref()
+        .map(|module| module.to_token_stream().to_string());
+    let lints = filter.lint_attrs();
+    let invocation = Location::call_site();
+    let scope = invocation.scope();
+    let (selected, isolated, earlier) = with_registry(|registry| {
+        let mut selected =
+            registry.expand(invocation.clone(), module.as_deref(), |name, functions| {
+                filter.selects(name, functions)
+            });
+        // Overloads marked `no_macro` were only registered to be checked against the others.
+        for functions in selected.values_mut() {
+            functions.retain(|data| !data.no_macro);
+        }
+        selected.retain(|_, functions| !functions.is_empty());
+        // Macros of local sets are scoped to their block, so other bodies can generate the same names, and so are the
+        // macros of other modules.
+        let earlier = selected
+            .iter()
+            .filter(|_| !filter.local)
+            .filter_map(|(name, functions)| {
+                let name = filter.macro_name(name);
+                let earlier = registry.emit(&name, &invocation, scope.as_ref()?, functions)?;
+                Some((name, earlier))
+            })
+            .collect::<std::collections::HashMap<_, _>>();
+        (
+            selected,
+            registry.generated.is_empty() && registry.pending.is_empty(),
+            earlier,
+        )
+    });
+    // The sets come sorted by name, or by their first overload with `order = registration`.
+    let mut selected = selected.into_iter().collect::<Vec<_>>();
+    if filter.registration_order {
+        selected.sort_by_key(|(_, functions)| functions.iter().map(|data| data.order).min());
+    }
+    let mut macros = selected
+        .iter()
+        .map(|(name, functions)| {
+            let name = filter.macro_name(name);
+            let regenerated = earlier.get(&name).map(|earlier| {
+                let message = format!(
+                    "`{}!` was already generated by the `macros!()` at {}, and generating it again for the overloads \
+                     registered since then would conflict with it: move that `macros!()` below them, or give them a \
+                     set of their own with `overload_set`",
+                    name, earlier
+                );
+                syn::Error::new(proc_macro2::Span::call_site(), message).into_compile_error()
+            });
+            regenerated
+                .or_else(|| shadowing_error(&name, functions))
+                .or_else(|| module_clash(&name, functions))
+                .unwrap_or_else(|| overload_macro(&name, functions, filter.vis.as_ref(), lints.as_ref()))
+        })
+        .map(TokenStream::from)
+        .collect::<TokenStream>();
+    if let Some(module) = &filter.docs {
+        let sets = selected
+            .iter()
+            .map(|(name, functions)| (filter.macro_name(name), functions.as_slice()));
+        macros.extend(TokenStream::from(doc_page(module, sets)));
+    }
+    if let Some(module) = &filter.prelude {
+        let sets = selected
+            .iter()
+            .map(|(name, functions)| (filter.macro_name(name), functions.as_slice()));
+        let export = matches!(filter.vis, Some(syn::Visibility::Public(_)));
+        macros.extend(TokenStream::from(prelude(module, sets, export)));
+    }
+    // An expansion that can't see any overload, as IDEs expanding the invocation on its own do, still succeeds so
+    // the errors point at the calls of the missing macros.
+    if isolated {
+        macros.extend(TokenStream::from(warning(
+            "`macros!()` found no overloads registered before it in this crate, so it generates no macros: place it \
+             after the `#[overload]` functions and the `mod` items declaring them",
+        )));
+    } else if selected.is_empty() {
+        macros.extend(TokenStream::from(warning(
+            "`macros!()` generates no macros: the sets registered before it were already generated by an earlier \
+             `macros!()` or aren't selected by this one, and overloads declared after it need a `macros!()` below \
+             them",
+        )));
+    }
+    if filter.persist {
+        if let Err(error) = persist() {
+            macros.extend(TokenStream::from(error.into_compile_error()));
+        }
+    }
+    if let Some(dir) = std::env::var_os("OVERFN_REPORT") {
+        // Every crate of the package writes its own file, so the report of the library isn't overwritten by the one of
+        // the integration tests.
+        let krate = std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "crate".to_string());
+        let path = std::path::Path::new(&dir).join(format!("{}.md", krate));
+        if let Err(error) =
+            std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, report()))
+        {
+            let message = format!(
+                "couldn't write the overload report to `{}`: {}",
+                path.display(),
+                error
+            );
+            macros.extend(TokenStream::from(warning(&message)));
+        }
+    }
+    macros
+}
+
+/// Directory where `macros!(persist)` saves the sets of `krate`, in the `OUT_DIR` that Cargo sets for every target of
+/// a package with a build script.
+fn persisted_dir(krate: &str) -> syn::Result<std::path::PathBuf> {
+    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`macros!(persist)` and `macros!(import = \"..\")` keep the sets in `OUT_DIR`, which Cargo only sets for \
+             packages with a build script: add a `build.rs` with an empty `fn main() {}`",
+        )
+    })?;
+    Ok(std::path::Path::new(&out_dir).join("overfn").join(krate))
+}
+
+/// Saves the sets of the `macros!(persist)` invocations of the file of the current one, in a file named after it.
+fn persist() -> syn::Result<()> {
+    let (file, sets) = with_registry(|registry| registry.persist(Location::call_site()));
+    let dir = persisted_dir(&std::env::var("CARGO_CRATE_NAME").unwrap_or_default())?;
+    let key = file
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect::<String>();
+    std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(dir.join(key), persisted_sets(&sets)))
+        .map_err(|error| {
+            let message = format!(
+                "couldn't save the overload sets to `{}`: {}",
+                dir.display(),
+                error
+            );
+            syn::Error::new(proc_macro2::Span::call_site(), message)
+        })
+}
+
+/// Macros of the sets saved by the `macros!(persist)` invocations of `krate`.
+fn import(krate: &proc_macro2::Ident, args: &MacrosArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let dir = persisted_dir(&krate.to_string())?;
+    let mut files = std::fs::read_dir(&dir)
+        .map_err(|_| {
+            let message = format!(
+                "no overload sets saved by `{}`: call `macros!(persist)` in it, in the same package",
+                krate
+            );
+            syn::Error::new(krate.span(), message)
+        })?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect::<Vec<_>>();
+    files.sort();
+    let mut macros = proc_macro2::TokenStream::new();
+    for file in files {
+        let persisted = std::fs::read_to_string(&file)
+            .map_err(|error| error.to_string())
+            .and_then(|text| {
+                text.parse()
+                    .map_err(|error: proc_macro2::LexError| error.to_string())
+            })
+            .map_err(|error| {
+                let message = format!(
+                    "couldn't read the overload sets saved at `{}`: {}",
+                    file.display(),
+                    error
+                );
+                syn::Error::new(krate.span(), message)
+            })?;
+        macros.extend(imported_macros(
+            persisted,
+            krate,
+            &args.prefix,
+            args.lint_attrs().as_ref(),
+            |name| args.filter.selects(name),
+        ));
+    }
+    Ok(macros)
+}
+
+/// Generate the macro of a single overload set registered with `#[overload(set)]`, without relying on the shared
+/// registry used by [`macros!()`](macro@macros).
+///
+/// Because nothing is stored between macro invocations, this works in doctests and when the compiler reuses the
+/// proc-macro server across crates. The generated macro accepts up to 16 arguments and every arity is forwarded to the
+/// corresponding overload, so calling an arity that wasn't overloaded fails to find the overload (or its
+/// `__overfn_{set}_{arity}` helper macro for free functions).
+///
+/// - `define_overloads!(add)` collects free functions overloaded with `#[overload(set)]` (or `#[overload(set = add)]`
+///   to group functions with another name) into `add!`.
+/// - `define_overloads!(Test::new)` generates `Test_new!` calling the associated functions `Test::new_N`.
+/// - `define_overloads!(Test.test)` generates `Test_test!` calling the methods `test_N` on the first argument. Use
+///   `define_overloads!(Test.test, skip_self)` for methods overloaded with `skip_self`.
+///
+/// # Example
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload(set)]
+/// fn add(item: usize) -> usize {
+///     10 + item
+/// }
+///
+/// #[overload(set)]
+/// fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// struct Test(usize);
+///
+/// impl Test {
+///     #[overload(Test, set)]
+///     fn new() -> Self {
+///         Self(0)
+///     }
+///
+///     #[overload(Test, set)]
+///     fn new(item: usize) -> Self {
+///         Self(item)
+///     }
+///
+///     #[overload(Test, set)]
+///     fn test(&self, other: usize) -> usize {
+///         self.0 + other
+///     }
+/// }
+///
+/// define_overloads!(add);
+/// define_overloads!(Test::new);
+/// define_overloads!(Test.test);
+///
+/// assert_eq!(add!(2), 12);
+/// assert_eq!(add!(2, 2), 4);
+/// assert_eq!(Test_new!().0, 0);
+/// assert_eq!(Test_test!(Test_new!(2), 2), 4);
+/// ```
+#[proc_macro]
+pub fn define_overloads(input: TokenStream) -> TokenStream {
+    let set = parse_macro_input!(input as SetPath);
+    set_macro(&set).into()
+}
+
+/// Call an overloaded function with the syntax of a plain call, routing it to the macro of its set: `call!(add(1, 2))`
+/// expands to `add!(1, 2)` and `call!(Test::new(2, 3))` to `Test_new!(2, 3)`, so existing calls can be migrated by
+/// wrapping them.
+///
+/// A path whose second to last segment starts with an uppercase letter is taken as `Type::name`, and the generic
+/// arguments of the type become the turbofish of the macro: `call!(Wrapper::<u8>::new(5))` expands to
+/// `Wrapper_new!(::<u8>, 5)`. The segments before it, or before the name of a free function, are kept as the path of
+/// the macro. Methods are called with the receiver as the first argument (`call!(Test::test(test, 2))`), because the
+/// type of a receiver isn't known when the macro expands.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// fn add(first: usize, second: usize) -> usize {
+///     first + second
+/// }
+///
+/// #[overload]
+/// fn add(first: usize, second: usize, third: usize) -> usize {
+///     first + second + third
+/// }
+///
+/// struct Test(usize);
+///
+/// impl Test {
+///     #[overload(Test)]
+///     fn new(left: usize, right: usize) -> Self {
+///         Self(left * right)
+///     }
+///
+///     #[overload(Test)]
+///     fn get(&self) -> usize {
+///         self.0
+///     }
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(call!(add(1, 2)), 3);
+/// assert_eq!(call!(add(1, 2, 3)), 6);
+/// let test = call!(Test::new(2, 3));
+/// assert_eq!(call!(Test::get(test)), 6);
+/// # }
+/// ```
+#[proc_macro]
+pub fn call(item: TokenStream) -> TokenStream {
+    let call = parse_macro_input!(item as Expr);
+    call_macro(&call)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Receiver of a qualified method call borrowed the way the method call syntax borrows it, expanded by the macros
+/// generated by [`macros!()`](macro@macros).
+#[doc(hidden)]
+#[proc_macro]
+pub fn __overfn_receiver(input: TokenStream) -> TokenStream {
+    let receiver = parse_macro_input!(input as BorrowedReceiver);
+    expand::borrowed_receiver(&receiver).into()
+}
+
+/// Call of the overload of a `keywords` set taking the arguments given by name, expanded by the macros generated by
+/// [`macros!()`](macro@macros).
+#[doc(hidden)]
+#[proc_macro]
+pub fn __overfn_keywords(input: TokenStream) -> TokenStream {
+    let call = parse_macro_input!(input as KeywordCall);
+    expand::keyword_call(&call).into()
+}
+
+/// Call of the overload of a `by_literal` set whose parameter types fit the literal arguments, expanded by the
+/// macros generated by [`macros!()`](macro@macros).
+#[doc(hidden)]
+#[proc_macro]
+pub fn __overfn_literal(input: TokenStream) -> TokenStream {
+    let call = parse_macro_input!(input as LiteralCall);
+    expand::literal_call(&call).into()
+}
+
+/// Lock in the behavior of an overload set: `test_suite!(add, cases = { (1) => 11, (1, 2) => 3 })` expands to a
+/// `#[cfg(test)]` module named `add_suite` with a test per case, calling `add!` with the arguments of the case and
+/// asserting that the result equals the expected value.
+///
+/// Invoke it after [`macros!()`](macro@macros) in the module of the set or in one of its descendants, so the macro is
+/// in scope and the renamed functions can be imported from the parent module. Methods take the receiver as their first
+/// argument, as in the macro.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///     10 + item
+/// }
+///
+/// #[overload]
+/// fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// test_suite!(add, cases = { (1) => 11, (1, 2) => 3 });
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn test_suite(input: TokenStream) -> TokenStream {
+    let suite = parse_macro_input!(input as TestSuite);
+    expand::test_suite(&suite).into()
+}
+
+/// Check the overloads of the whole crate, failing with a single error that lists every inconsistency found:
+///
+/// - sets registered after the last [`macros!()`](macro@macros), whose macro is never generated;
+/// - free functions sharing the macro name of methods (a free `Test_new` next to `#[overload(Test)] fn new`);
+/// - overloads the macro never calls, because the `into` option of another overload takes the same number of
+///   arguments with a higher `priority`;
+/// - `self_overload!` calls of an arity that no overload of the set takes;
+/// - several overloads of a set keeping the original name with `default`.
+///
+/// Place it at the end of the crate root, after the modules declaring overloads, so that their attributes have already
+/// been expanded. It only sees what was registered by the time it expands. Overloads behind a disabled `#[cfg]` are
+/// never registered (see [conditional overloads](macro@overload#conditional-overloads)), so they leave no option
+/// behind to check.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///     item
+/// }
+///
+/// macros!();
+///
+/// audit!();
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// use overfn::*;
+///
+/// macros!();
+///
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///     item
+/// }
+///
+/// audit!();
+/// # fn main() {}
+/// ```
+///
+/// The macro names of a free `Test_new` and of the method `new` of `Test` collide:
+///
+/// ```compile_fail
+/// use overfn::*;
+///
+/// pub struct Test;
+///
+/// #[overload]
+/// #[allow(non_snake_case)]
+/// fn Test_new(value: usize) -> usize {
+///     value
+/// }
+///
+/// impl Test {
+///     #[overload(Test)]
+///     fn new(left: usize, right: usize) -> usize {
+///         left + right
+///     }
+/// }
+///
+/// macros!();
+///
+/// audit!();
+/// # fn main() {}
+/// ```
+///
+/// The arity-2 option that `into(2)` adds with `priority = 1` takes over the explicit overload, which is never called:
+///
+/// ```compile_fail
+/// use overfn::*;
+///
+/// pub struct Config(u16);
+///
+/// impl From<(&str, u16)> for Config {
+///     fn from((_, port): (&str, u16)) -> Self {
+///         Self(port)
+///     }
+/// }
+///
+/// #[overload(into(2), priority = 1)]
+/// fn open(config: Config) -> u16 {
+///     config.0
+/// }
+///
+/// #[overload]
+/// fn open(_host: &str, port: u16) -> u16 {
+///     port
+/// }
+///
+/// macros!();
+///
+/// audit!();
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn audit(_item: TokenStream) -> TokenStream {
+    let problems = inconsistencies();
+    if problems.is_empty() {
+        return TokenStream::new();
+    }
+    let message = format!(
+        "overfn audit found {} problem(s):\n- {}",
+        problems.len(),
+        problems.join("\n- ")
+    );
+    quote! { ::core::compile_error!(#message); }.into()
+}
+
+/// Check that every overload registered in the crate made it into a macro, failing with an error that lists the sets
+/// registered after the last [`macros!()`](macro@macros): overloads in a module expanded after the `macros!()` meant
+/// to generate them would otherwise be missing from the macro without notice.
+///
+/// Place it at the end of the crate root, after the modules declaring overloads. Unlike [`audit!()`](macro@audit), it
+/// only checks that nothing was left behind.
+///
+/// ```compile_fail
+/// use overfn::*;
+///
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///     item
+/// }
+///
+/// macros!();
+///
+/// #[overload]
+/// fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// finalize_overloads!();
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn finalize_overloads(_item: TokenStream) -> TokenStream {
+    let problems = ungenerated();
+    if problems.is_empty() {
+        return TokenStream::new();
+    }
+    let message = format!(
+        "overloads registered after the last `macros!()`:\n- {}",
+        problems.join("\n- ")
+    );
+    quote! { ::core::compile_error!(#message); }.into()
+}
+
+/// List the overloads registered so far for a set, as an array of `(arity, path)` pairs ordered by arity, where the
+/// path is the renamed function as written to call it (`"add_2"`, `"Test::test_2"`), to build test harnesses or
+/// benchmarks going over every overload without listing them by hand. Methods count their receiver in the arity, as
+/// their macro does. The set is named by its macro (`overloads_of!(Test_test)`) and must be registered before the
+/// invocation, which fails to compile otherwise. [`#[overload(table)]`](macro@overload) generates function pointers
+/// instead, for overloads with nameable signatures.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// #[overload]
+/// fn add(item: usize) -> usize {
+///     item
+/// }
+///
+/// #[overload]
+/// fn add(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(overloads_of!(add), [(1, "add_1"), (2, "add_2")]);
+/// # }
+/// ```
+#[proc_macro]
+pub fn overloads_of(item: TokenStream) -> TokenStream {
+    let set = parse_macro_input!(item as proc_macro2::Ident);
+    match registered(&set.to_string()) {
+        Some(functions) => expand::overloads_of(&functions).into(),
+        None => {
+            let message = format!(
+                "no overloads of `{}` registered so far: `overloads_of!` must come after them",
+                set
+            );
+            syn::Error::new(set.span(), message)
+                .into_compile_error()
+                .into()
+        }
+    }
+}
+
+/// Call of the overload of the enclosing set taking as many arguments, from the body of a function marked with
+/// [`#[overload]`](macro@overload), which replaces it. Used anywhere else it fails to compile.
+#[proc_macro]
+pub fn self_overload(_: TokenStream) -> TokenStream {
+    let message =
+        "`self_overload!` only applies to the body of a function marked with `#[overload]`";
+    syn::Error::new(proc_macro2::Span::call_site(), message)
+        .into_compile_error()
+        .into()
+}
+
+/// Configure how the overloads of the crate are renamed. `mangle = "template"` names the overloads expanded after it
+/// by the template, where `{name}` stands for the function name and `{arity}` for its arity: with
+/// `config!(mangle = "__{name}_ov{arity}")`, `test` taking two arguments becomes `__test_ov2`. The macros generated by
+/// [`macros!()`](macro@macros) call the overloads by the names they were given, so only the renamed functions change.
+///
+/// Call it at the top of the crate root, before any overload. Overloads of the same set expanded before and after it
+/// keep the names they got. The scheme doesn't apply to methods of trait impls, which keep their name, nor to the
+/// stateless sets of [`define_overloads!`](macro@define_overloads), which look the overloads up by their default name.
+/// `mangle = hidden` still prefixes the result with `__overfn_`, and overloads told apart by `by_len`, `by_literal` or
+/// `op` still get the length or the types after it.
+///
+/// `inline` marks the overloads expanded after it with `#[inline(always)]`, in place of their own `inline` attributes,
+/// for benchmarks and other performance-sensitive code that must not pay for the macro layer. The macros already
+/// expand to a direct call of the overload; under `inline`, [`macros!()`](macro@macros) also coerces the path it calls
+/// to a function pointer of the signature of the overload (`const _: fn(&str) -> usize = parse_1;`), so an overload
+/// reached through an adapter fails to compile. Methods and overloads whose signature can't be named outside of them
+/// keep the attribute without the check.
+///
+/// ```rust
+/// use overfn::*;
+///
+/// config!(mangle = "{name}_with_{arity}");
+///
+/// #[overload]
+/// fn parse(text: &str) -> usize {
+///     text.len()
+/// }
+///
+/// #[overload]
+/// fn parse(text: &str, radix: u32) -> u32 {
+///     u32::from_str_radix(text, radix).unwrap()
+/// }
+///
+/// fn parse_2(left: usize, right: usize) -> usize {
+///     left + right
+/// }
+///
+/// macros!();
+///
+/// # fn main() {
+/// assert_eq!(parse!("ff", 16), 255);
+/// assert_eq!(parse_with_1("abc"), 3);
+/// assert_eq!(parse_2(1, 2), 3);
+/// # }
+/// ```
+#[proc_macro]
+pub fn config(item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(item as ConfigArgs);
+    with_registry(|registry| {
+        if let Some(mangling) = args.mangle {
+            registry.mangling = Some(mangling);
+        }
+        registry.inline |= args.inline;
+    });
+    TokenStream::new()
 }
@@ -0,0 +1,971 @@
+//! Registry shared between the [`overload`](macro@crate::overload) attribute and [`macros!()`](macro@crate::macros).
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    env, fmt, fs,
+    hash::{Hash, Hasher},
+    sync::{Mutex, PoisonError},
+};
+
+use quote::ToTokens;
+use syn::{Expr, ExprLit, FnArg, Ident, ItemFn, Lit, Meta, Pat, Receiver, ReturnType, Visibility};
+
+use crate::expand::tidy;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub(crate) enum ArgType {
+    Struct(String),
+    /// Method of an inherent impl of the type.
+    Instance(String),
+    /// Method of a trait impl, called through its qualified path (`<Money as From<usize>>`), with the type and the trait
+    /// kept apart so only the type is qualified in exported macros.
+    Trait(String, String),
+    #[default]
+    Other,
+}
+
+/// How a method takes its receiver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ReceiverKind {
+    Value,
+    Ref,
+    Mut,
+}
+
+impl ReceiverKind {
+    /// Receiver as written in a signature.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Value => "self",
+            Self::Ref => "&self",
+            Self::Mut => "&mut self",
+        }
+    }
+
+    fn of(receiver: &Receiver) -> Self {
+        match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) if receiver.colon_token.is_none() => Self::Mut,
+            (Some(_), None) if receiver.colon_token.is_none() => Self::Ref,
+            _ => Self::Value,
+        }
+    }
+}
+
+/// Source location of a registered overload, used to point at previous definitions in error messages.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Location {
+    file: String,
+    line: usize,
+    column: usize,
+}
+
+impl Location {
+    pub(crate) fn of(ident: &Ident) -> Self {
+        let span = ident.span().unwrap();
+        Self {
+            file: span.file(),
+            line: span.line(),
+            column: span.column(),
+        }
+    }
+
+    /// Location of the macro invocation being expanded.
+    pub(crate) fn call_site() -> Self {
+        let span = proc_macro::Span::call_site();
+        Self {
+            file: span.file(),
+            line: span.line(),
+            column: span.column(),
+        }
+    }
+
+    /// Location of the innermost brace enclosing this location in its source file, or the start of the file at its
+    /// root: the textual scope of a `macro_rules!` defined here. `None` when the file can't be read.
+    pub(crate) fn scope(&self) -> Option<Self> {
+        let source = fs::read_to_string(&self.file).ok()?;
+        let chars = source.chars().collect::<Vec<_>>();
+        let mut positions = Vec::with_capacity(chars.len());
+        let (mut line, mut column) = (1, 1);
+        for &c in &chars {
+            positions.push((line, column));
+            match c {
+                '\n' => (line, column) = (line + 1, 1),
+                _ => column += 1,
+            }
+        }
+        let ident = |i: usize| chars[i].is_alphanumeric() || chars[i] == '_';
+        let mut braces = Vec::new();
+        let mut i = 0;
+        while i < chars.len() && positions[i] < (self.line, self.column) {
+            match (chars[i], chars.get(i + 1)) {
+                ('/', Some('/')) => {
+                    while i < chars.len() && chars[i] != '\n' {
+                        i += 1;
+                    }
+                }
+                ('/', Some('*')) => {
+                    let mut depth = 0;
+                    while i + 1 < chars.len() {
+                        match (chars[i], chars[i + 1]) {
+                            ('/', '*') => depth += 1,
+                            ('*', '/') => depth -= 1,
+                            _ => {
+                                i += 1;
+                                continue;
+                            }
+                        }
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                ('"', _) => {
+                    i += 1;
+                    while i < chars.len() && chars[i] != '"' {
+                        i += if chars[i] == '\\' { 2 } else { 1 };
+                    }
+                }
+                // Raw strings, also after the `b` and `c` prefixes, end at a quote followed by as many hashes.
+                ('r', Some('#' | '"'))
+                    if i == 0
+                        || !ident(i - 1)
+                        || matches!(chars[i - 1], 'b' | 'c') && (i == 1 || !ident(i - 2)) =>
+                {
+                    let hashes = chars[i + 1..].iter().take_while(|&&c| c == '#').count();
+                    if chars.get(i + 1 + hashes) == Some(&'"') {
+                        i += 2 + hashes;
+                        let closes = |i: usize| {
+                            chars[i + 1..].iter().take_while(|&&c| c == '#').count() >= hashes
+                        };
+                        while i < chars.len() && !(chars[i] == '"' && closes(i)) {
+                            i += 1;
+                        }
+                        i += hashes;
+                    }
+                }
+                // Character literals, unlike lifetimes and labels, close their quote.
+                ('\'', Some('\\')) => {
+                    i += 3;
+                    while i < chars.len() && chars[i] != '\'' {
+                        i += 1;
+                    }
+                }
+                ('\'', Some(_)) if chars.get(i + 2) == Some(&'\'') => i += 2,
+                ('{', _) => braces.push(positions[i]),
+                ('}', _) => {
+                    braces.pop();
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        let (line, column) = braces.pop().unwrap_or((0, 0));
+        Some(Self {
+            file: self.file.clone(),
+            line,
+            column,
+        })
+    }
+
+    /// Whether `data` is the same item as `other` expanded again, as written at the same location with the same
+    /// arity, which `into` adapters sharing the location of their function don't have.
+    fn same_item(&self, n_args: usize, other: &FunctionData) -> bool {
+        *self == other.location && n_args == other.n_args
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// Conversion applied by the macro to the arguments before calling the overload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) enum Convert {
+    #[default]
+    None,
+    /// Every argument goes through `Into::into`.
+    Into,
+    /// All the arguments are packed into a tuple that goes through `Into::into`, for the single parameter of the
+    /// function.
+    Pack,
+}
+
+/// Whether an overload is an `unsafe fn`, and who writes the `unsafe` block around its call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) enum Safety {
+    #[default]
+    Safe,
+    /// The macro expands to the bare call, which the caller wraps in an `unsafe` block.
+    Unsafe,
+    /// `wrap_unsafe`: the macro wraps the call in an `unsafe` block itself.
+    Wrapped,
+}
+
+/// How far an overload is visible, ordered from least to most visible.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum Reach {
+    #[default]
+    Private,
+    Restricted,
+    Crate,
+    Public,
+}
+
+impl Reach {
+    fn of(vis: &Visibility) -> Self {
+        match vis {
+            Visibility::Public(_) => Self::Public,
+            Visibility::Restricted(restricted) if restricted.path.is_ident("crate") => Self::Crate,
+            Visibility::Restricted(restricted) if restricted.path.is_ident("self") => Self::Private,
+            Visibility::Restricted(_) => Self::Restricted,
+            Visibility::Inherited => Self::Private,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub(crate) struct FunctionData {
+    pub(crate) name: String,
+    pub(crate) n_args: usize,
+    pub(crate) arg_type: ArgType,
+    pub(crate) location: Location,
+    pub(crate) receiver: Option<ReceiverKind>,
+    /// Call the method on a copy of the receiver.
+    pub(crate) copy_receiver: bool,
+    /// Call the method of an inherent impl with the method call syntax instead of its qualified path.
+    pub(crate) method_call: bool,
+    /// Implement the extension trait of the set for the overload.
+    pub(crate) ext: bool,
+    /// Implement the argument trait of the inherent `_any` method of the set for the overload.
+    pub(crate) inherent: bool,
+    pub(crate) convert: Convert,
+    pub(crate) safety: Safety,
+    /// Positions of the arguments after the receiver borrowed mutably by the macro.
+    pub(crate) places: Vec<usize>,
+    /// Types of the arguments after the receiver, kept as tokens because `syn` types can't be shared between macro
+    /// invocations.
+    pub(crate) arg_types: Vec<String>,
+    /// Names of the arguments after the receiver, `arg_N` for patterns.
+    pub(crate) arg_names: Vec<String>,
+    /// Return type, `None` for functions returning `()`.
+    pub(crate) output: Option<String>,
+    /// First line of the doc comment of the function, listed on the documentation page of the set.
+    pub(crate) summary: Option<String>,
+    /// Whether the function declares generic parameters of its own or belongs to a generic impl, so its signature
+    /// can't be named outside of it.
+    pub(crate) generic: bool,
+    /// Method of the definition of the trait named as its type, called on any type implementing the trait.
+    pub(crate) trait_def: bool,
+    /// Number of type and const parameters of the generic impl of the overload given with `generic(..)`, inferred
+    /// through placeholders in the path the macro calls.
+    pub(crate) impl_params: usize,
+    /// Generic arguments of the type of the impl as written in the attribute, `<Phase1>`, passed to it in the path the
+    /// macro calls.
+    pub(crate) type_args: Option<String>,
+    /// Path of the module defining the overload below the crate root, given with `module = path`.
+    pub(crate) module: Option<String>,
+    /// Visibility as written, empty for private functions.
+    pub(crate) vis: String,
+    pub(crate) reach: Reach,
+    /// Generate the argument enum and dispatcher function of the set.
+    pub(crate) dispatch: bool,
+    /// Generate the function pointer table of the set.
+    pub(crate) table: bool,
+    /// Generate the macro named after the type of the set.
+    pub(crate) ctor: bool,
+    /// Implement `Default` for the type through the overload.
+    pub(crate) derive_default: bool,
+    /// Implement `From` or `TryFrom` for the type through the overload.
+    pub(crate) derive_from: bool,
+    /// Operator trait of `core::ops` implemented for the argument types through the overload.
+    pub(crate) op: Option<String>,
+    /// Enum generated for the set, wrapping the result of every call in the variant of its arity.
+    pub(crate) unify_return: Option<String>,
+    /// Defaults of the parameters given by `keywords`, by name, when the macro takes the arguments of the overload by
+    /// parameter name.
+    pub(crate) keywords: Option<Vec<(String, String)>>,
+    /// Renamed under `config!(inline)`, so the macros check that they call the function as is.
+    pub(crate) inline: bool,
+    /// A function under the original name calls the overload.
+    pub(crate) alias: bool,
+    /// Generate the pyo3 function of the set.
+    pub(crate) pyo3: bool,
+    /// Generate the wasm-bindgen function of the set.
+    pub(crate) wasm: bool,
+    /// Generated by `macros!(local)` instead of `macros!()`.
+    pub(crate) local: bool,
+    /// Renamed and checked against the other overloads of the set, but left out of its macro.
+    pub(crate) no_macro: bool,
+    /// Export the set with `#[macro_export]`.
+    pub(crate) export: bool,
+    /// Leave the overload out of the exported macro.
+    pub(crate) internal: bool,
+    /// Length of the array parameter of a `by_len` overload, dispatched by the type of the arguments between the
+    /// overloads of the same arity.
+    pub(crate) array_len: Option<usize>,
+    /// Told apart from the other `by_literal` overloads of the same arity by the types of literal arguments.
+    pub(crate) by_literal: bool,
+    /// Registered with [`overload_set`](macro@crate::overload_set), under a macro name chosen for it.
+    pub(crate) grouped: bool,
+    /// Order of the option among the options of the same arity, the highest first.
+    pub(crate) priority: i32,
+    /// Hash of the attribute arguments and the item as written, to recognize the same item expanded again.
+    pub(crate) content: u64,
+    /// Position of the overload among the overloads registered in the crate, for `macros!(order = registration)`.
+    pub(crate) order: usize,
+    /// Renamed overloads its body calls through `self_overload!`, checked by [`audit!()`](macro@crate::audit).
+    pub(crate) self_calls: Vec<String>,
+}
+
+/// Names of the parameters of a function after its receiver, `arg_N` for those taking a pattern, `N` counting from 0
+/// after the receiver.
+pub(crate) fn param_names(function: &ItemFn) -> Vec<String> {
+    function
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(arg) => Some(arg),
+            FnArg::Receiver(_) => None,
+        })
+        .enumerate()
+        .map(|(i, arg)| match &*arg.pat {
+            Pat::Ident(pat) => pat.ident.to_string(),
+            _ => format!("arg_{}", i),
+        })
+        .collect()
+}
+
+impl FunctionData {
+    pub(crate) fn new(name: String, arg: ArgType, function: &ItemFn) -> Self {
+        Self {
+            name,
+            n_args: function.sig.inputs.len(),
+            arg_type: arg,
+            location: Location::of(&function.sig.ident),
+            convert: Convert::None,
+            safety: match function.sig.unsafety {
+                Some(_) => Safety::Unsafe,
+                None => Safety::Safe,
+            },
+            places: Vec::new(),
+            copy_receiver: false,
+            method_call: false,
+            ext: false,
+            inherent: false,
+            receiver: match function.sig.inputs.first() {
+                Some(FnArg::Receiver(receiver)) => Some(ReceiverKind::of(receiver)),
+                _ => None,
+            },
+            arg_types: function
+                .sig
+                .inputs
+                .iter()
+                .filter_map(|input| match input {
+                    FnArg::Typed(arg) => Some(arg.ty.to_token_stream().to_string()),
+                    FnArg::Receiver(_) => None,
+                })
+                .collect(),
+            arg_names: param_names(function),
+            output: match &function.sig.output {
+                ReturnType::Default => None,
+                ReturnType::Type(_, ty) => Some(ty.to_token_stream().to_string()),
+            },
+            summary: function.attrs.iter().find_map(|attr| match &attr.meta {
+                Meta::NameValue(doc) if doc.path.is_ident("doc") => match &doc.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(line),
+                        ..
+                    }) => Some(line.value().trim().to_string()).filter(|line| !line.is_empty()),
+                    _ => None,
+                },
+                _ => None,
+            }),
+            generic: !function.sig.generics.params.is_empty(),
+            trait_def: false,
+            impl_params: 0,
+            type_args: None,
+            module: None,
+            vis: function.vis.to_token_stream().to_string(),
+            reach: Reach::of(&function.vis),
+            dispatch: false,
+            table: false,
+            ctor: false,
+            derive_default: false,
+            derive_from: false,
+            op: None,
+            unify_return: None,
+            keywords: None,
+            inline: false,
+            alias: false,
+            pyo3: false,
+            wasm: false,
+            local: false,
+            no_macro: false,
+            export: false,
+            internal: false,
+            array_len: None,
+            by_literal: false,
+            grouped: false,
+            priority: 0,
+            content: 0,
+            order: 0,
+            self_calls: Vec::new(),
+        }
+    }
+
+    /// Parameters of the overload as written, for diagnostics: `(&self, usize)`.
+    fn parameters(&self) -> String {
+        let receiver = self.receiver.map(ReceiverKind::as_str).map(str::to_owned);
+        let types = self.arg_types.iter().map(|ty| tidy(ty));
+        let parameters = receiver.into_iter().chain(types).collect::<Vec<_>>();
+        format!("({})", parameters.join(", "))
+    }
+}
+
+/// Scheme of the names given to the overloads, set with [`config!`](macro@crate::config): a template of the name
+/// where `{name}` stands for the function name and `{arity}` for its arity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Mangling(pub(crate) String);
+
+impl Mangling {
+    pub(crate) fn apply(&self, name: &str, arity: usize) -> String {
+        self.0
+            .replace("{name}", name)
+            .replace("{arity}", &arity.to_string())
+    }
+}
+
+/// Overloads of a crate.
+#[derive(Default)]
+pub(crate) struct Registry {
+    /// Overloads registered since the last [`macros!()`](macro@crate::macros), by macro name. A `BTreeMap` keeps the
+    /// generated macros in a stable order.
+    pub(crate) pending: BTreeMap<String, Vec<FunctionData>>,
+    /// Sets generated by [`macros!()`](macro@crate::macros) so far, checked by [`audit!()`](macro@crate::audit).
+    pub(crate) generated: Vec<(String, Vec<FunctionData>)>,
+    /// Sets generated by each [`macros!()`](macro@crate::macros) invocation, generated again when tools such as
+    /// rust-analyzer expand the invocation again after the overloads have left `pending`.
+    expansions: HashMap<Location, BTreeMap<String, Vec<FunctionData>>>,
+    /// Invocation of [`macros!()`](macro@crate::macros) that last generated each macro in each textual scope, outside
+    /// of function bodies, with the names of the overloads it calls.
+    emitted: HashMap<(String, Location), (Location, Vec<String>)>,
+    /// Invocations of `macros!(persist)`, whose sets are saved for other compilations of the package.
+    persisted: Vec<Location>,
+    /// Naming scheme of the overloads expanded after the last [`config!`](macro@crate::config).
+    pub(crate) mangling: Option<Mangling>,
+    /// Mark the overloads expanded after `config!(inline)` with `#[inline(always)]`.
+    pub(crate) inline: bool,
+    /// Overloads registered so far, numbering each new one in its `order`.
+    registered: usize,
+}
+
+impl Registry {
+    /// Moves the pending sets `selects` accepts to the sets generated by the [`macros!()`](macro@crate::macros)
+    /// invocation at `invocation`, and returns all the sets of the invocation, including those of earlier expansions of
+    /// the same invocation. With a `module`, only the overloads declared in it are taken from each set, leaving the
+    /// others pending.
+    pub(crate) fn expand(
+        &mut self,
+        invocation: Location,
+        module: Option<&str>,
+        selects: impl Fn(&str, &[FunctionData]) -> bool,
+    ) -> BTreeMap<String, Vec<FunctionData>> {
+        let fresh = match module {
+            None => {
+                let (fresh, rest) = std::mem::take(&mut self.pending)
+                    .into_iter()
+                    .partition::<BTreeMap<_, _>, _>(|(name, functions)| selects(name, functions));
+                self.pending = rest;
+                fresh
+            }
+            Some(module) => {
+                let mut fresh = BTreeMap::new();
+                for (name, functions) in std::mem::take(&mut self.pending) {
+                    let (claimed, rest) = functions
+                        .iter()
+                        .cloned()
+                        .partition::<Vec<_>, _>(|data| data.module.as_deref() == Some(module));
+                    match claimed.is_empty() || !selects(&name, &claimed) {
+                        true => {
+                            self.pending.insert(name, functions);
+                        }
+                        false => {
+                            if !rest.is_empty() {
+                                self.pending.insert(name.clone(), rest);
+                            }
+                            fresh.insert(name, claimed);
+                        }
+                    }
+                }
+                fresh
+            }
+        };
+        let mut sets = self.expansions.remove(&invocation).unwrap_or_default();
+        for (name, functions) in &fresh {
+            let set = sets.entry(name.clone()).or_default();
+            for data in functions {
+                set.retain(|other| !data.location.same_item(data.n_args, other));
+                set.push(data.clone());
+            }
+            set.sort_by_key(|data| (data.n_args, Reverse(data.priority)));
+        }
+        self.generated.extend(fresh);
+        self.expansions.insert(invocation, sets.clone());
+        sets
+    }
+
+    /// Records that the invocation at `invocation` generates the macro `name` for `functions` in the textual scope
+    /// `scope`, and returns the earlier invocation that already generated a macro of that name in the same scope for
+    /// other overloads, whose items the new one would conflict with. An invocation generating some of the same overloads
+    /// is the earlier one expanded again after it moved, as in IDEs, and takes its place.
+    pub(crate) fn emit(
+        &mut self,
+        name: &str,
+        invocation: &Location,
+        scope: &Location,
+        functions: &[FunctionData],
+    ) -> Option<Location> {
+        let names = functions
+            .iter()
+            .map(|data| data.name.clone())
+            .collect::<Vec<_>>();
+        let key = (name.to_owned(), scope.clone());
+        match self.emitted.get(&key) {
+            Some((earlier, earlier_names))
+                if earlier != invocation
+                    && !names.iter().any(|name| earlier_names.contains(name)) =>
+            {
+                Some(earlier.clone())
+            }
+            _ => {
+                self.emitted.insert(key, (invocation.clone(), names));
+                None
+            }
+        }
+    }
+
+    /// Marks the invocation at `invocation` as persisted, and returns its source file with the sets generated by all
+    /// the persisted invocations of the file, in source order.
+    pub(crate) fn persist(
+        &mut self,
+        invocation: Location,
+    ) -> (String, Vec<(String, Vec<FunctionData>)>) {
+        if !self.persisted.contains(&invocation) {
+            self.persisted.push(invocation.clone());
+            self.persisted.sort();
+        }
+        let sets = self
+            .persisted
+            .iter()
+            .filter(|location| location.file == invocation.file)
+            .filter_map(|location| self.expansions.get(location))
+            .flat_map(|sets| {
+                sets.iter()
+                    .map(|(name, functions)| (name.clone(), functions.clone()))
+            })
+            .collect();
+        (invocation.file, sets)
+    }
+}
+
+/// Registries by crate, so crates expanded by the same proc-macro server don't see each other's overloads. A
+/// `BTreeMap` can be built in a `static` without lazy initialization.
+static REGISTRIES: Mutex<BTreeMap<String, Registry>> = Mutex::new(BTreeMap::new());
+
+/// Runs `f` on the registry of the crate being compiled, told apart by the package and crate names Cargo passes to the
+/// compiler. Builds outside of Cargo share a single registry.
+pub(crate) fn with_registry<R>(f: impl FnOnce(&mut Registry) -> R) -> R {
+    let var = |name| env::var(name).unwrap_or_default();
+    let krate = format!("{}/{}", var("CARGO_PKG_NAME"), var("CARGO_CRATE_NAME"));
+    // A panic while the lock was held can't leave a registry half updated for the next expansion to trip over.
+    let mut registries = REGISTRIES.lock().unwrap_or_else(PoisonError::into_inner);
+    f(registries.entry(krate).or_default())
+}
+
+/// Adds an overload to the registry read by [`macros!()`](macro@crate::macros), keeping the overloads of each set sorted
+/// by arity and then by descending priority so the options of the macro don't depend on the expansion order.
+///
+/// Registering the same item again at the same location, as tools re-running the attribute expansion do, leaves the
+/// registry unchanged, even once its set has been generated, and an item edited in place replaces its earlier version.
+///
+/// # Errors
+///
+/// Fails if the set already has an overload with the same number of arguments and priority, unless `replace` is set or
+/// both are `by_len` overloads taking arrays of different lengths.
+/// The error points at the new definition and names the location of the first one, since spans don't outlive the
+/// expansion that created them.
+pub(crate) fn register(
+    ident: &Ident,
+    macro_ident: String,
+    data: FunctionData,
+    replace: bool,
+) -> syn::Result<()> {
+    with_registry(|registry| {
+        let expanded = registry
+            .generated
+            .iter()
+            .filter(|(name, _)| *name == macro_ident)
+            .flat_map(|(_, functions)| functions)
+            .any(|other| {
+                data.location.same_item(data.n_args, other) && other.content == data.content
+            });
+        if expanded {
+            return Ok(());
+        }
+        let edited = |other: &FunctionData| data.location.same_item(data.n_args, other);
+        for (name, functions) in &mut registry.generated {
+            if *name == macro_ident {
+                functions.retain(|other| !edited(other));
+            }
+        }
+        for sets in registry.expansions.values_mut() {
+            if let Some(functions) = sets.get_mut(&macro_ident) {
+                functions.retain(|other| !edited(other));
+            }
+        }
+        let mut data = data;
+        data.order = registry.registered;
+        registry.registered += 1;
+        let set = registry.pending.entry(macro_ident).or_default();
+        insert(set, ident, data, replace)
+    })
+}
+
+/// Whether the macro can't tell `data` and `other` apart by their arguments, so only the one with the highest priority
+/// is called.
+fn same_slot(data: &FunctionData, other: &FunctionData) -> bool {
+    let by_len = other.array_len.is_some() && data.array_len.is_some();
+    let by_literal = other.by_literal && data.by_literal;
+    let by_op = other.op.is_some() && other.op == data.op;
+    // Overloads declared in different modules are generated apart with `macros!(module = path)`.
+    other.n_args == data.n_args
+        && other.module == data.module
+        && (!by_len || other.array_len == data.array_len)
+        && (!by_literal || other.arg_types == data.arg_types)
+        && (!by_op || other.arg_types == data.arg_types)
+}
+
+/// Adds an overload to the overloads of its set, as described by [`register`].
+fn insert(
+    set: &mut Vec<FunctionData>,
+    ident: &Ident,
+    data: FunctionData,
+    replace: bool,
+) -> syn::Result<()> {
+    set.retain(|other| {
+        !data.location.same_item(data.n_args, other) || other.content == data.content
+    });
+    let duplicate = set
+        .iter()
+        .position(|other| other.priority == data.priority && same_slot(other, &data));
+    match duplicate {
+        Some(index)
+            if set[index].content == data.content && set[index].location == data.location => {}
+        Some(index) if replace => set[index] = data,
+        Some(index) => {
+            // Overloads telling their arguments apart by type are the likely intent, so the error shows the types.
+            let types = match set[index].arg_types != data.arg_types {
+                true => format!(
+                    ": `{}{}` and `{}{}` only differ by the types of their arguments, which overloads of the same \
+                     arity can't be told apart by; rename one, or mark both with `by_literal` if they are called \
+                     with literals",
+                    ident,
+                    set[index].parameters(),
+                    ident,
+                    data.parameters()
+                ),
+                false => String::new(),
+            };
+            let mut error = syn::Error::new(
+                ident.span(),
+                format!(
+                    "function `{}` with {} arguments already exists{}{}",
+                    ident,
+                    data.n_args,
+                    match data.array_len {
+                        Some(len) => format!(" for arrays of length {}", len),
+                        None => String::new(),
+                    },
+                    types
+                ),
+            );
+            error.combine(syn::Error::new(
+                ident.span(),
+                format!(
+                    "first defined at {}\n\
+                     use #[overload(override)] to replace the earlier definition",
+                    set[index].location
+                ),
+            ));
+            return Err(error);
+        }
+        None => {
+            let key = |data: &FunctionData| (data.n_args, Reverse(data.priority));
+            let index = set.partition_point(|other| key(other) <= key(&data));
+            set.insert(index, data);
+        }
+    }
+    Ok(())
+}
+
+/// Hash of an overloaded item identifying its content between expansions.
+pub(crate) fn content_hash(attr: &str, item: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    attr.hash(&mut hasher);
+    item.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Naming scheme of the overloads configured so far in the crate.
+pub(crate) fn mangling() -> Option<Mangling> {
+    with_registry(|registry| registry.mangling.clone())
+}
+
+/// Whether the overloads expanded now are marked with `#[inline(always)]`, after `config!(inline)`.
+pub(crate) fn inlined() -> bool {
+    with_registry(|registry| registry.inline)
+}
+
+/// Visibility of the least visible overload registered so far in a set.
+pub(crate) fn least_visible(macro_ident: &str) -> Option<Visibility> {
+    with_registry(|registry| {
+        let data = registry
+            .pending
+            .get(macro_ident)?
+            .iter()
+            .min_by_key(|data| data.reach)?;
+        syn::parse_str(&data.vis).ok()
+    })
+}
+
+/// Overloads registered so far under the macro name `name`, for [`overloads_of!`](macro@crate::overloads_of): the
+/// pending ones if the set still waits for a [`macros!()`](macro@crate::macros), otherwise the last set generated under
+/// the name.
+pub(crate) fn registered(name: &str) -> Option<Vec<FunctionData>> {
+    with_registry(|registry| {
+        let generated = registry.generated.iter().rev();
+        registry.pending.get(name).cloned().or_else(|| {
+            generated
+                .filter(|(generated, _)| generated == name)
+                .map(|(_, functions)| functions.clone())
+                .next()
+        })
+    })
+}
+
+/// Sets registered so far that are still waiting for a [`macros!()`](macro@crate::macros), checked by
+/// [`finalize_overloads!()`](macro@crate::finalize_overloads).
+pub(crate) fn ungenerated() -> Vec<String> {
+    with_registry(|registry| never_generated(&registry.pending))
+}
+
+fn never_generated(pending: &BTreeMap<String, Vec<FunctionData>>) -> Vec<String> {
+    pending
+        .iter()
+        .filter_map(|(name, functions)| {
+            // Overloads without a macro don't wait for one.
+            let locations = functions
+                .iter()
+                .filter(|data| !data.no_macro)
+                .map(|data| data.location.to_string())
+                .collect::<Vec<_>>();
+            if locations.is_empty() {
+                return None;
+            }
+            Some(format!(
+                "`{}!` is never generated: its overloads at {} are registered after the last `macros!()`",
+                name,
+                locations.join(", ")
+            ))
+        })
+        .collect()
+}
+
+/// Inconsistencies of the overloads registered so far: sets still waiting for a [`macros!()`](macro@crate::macros)
+/// that will never come, macro names shared by free functions and methods of a type named like `Type_name` outside of
+/// [`overload_set`](macro@crate::overload_set), overloads the macro never calls because another one of the same
+/// arguments has a higher priority, `self_overload!` calls of unregistered overloads, and `default` functions kept
+/// under the same name by several overloads.
+pub(crate) fn inconsistencies() -> Vec<String> {
+    with_registry(|registry| {
+        let (pending, generated) = (&registry.pending, &registry.generated);
+        let mut problems = never_generated(pending);
+
+        let sets = generated
+            .iter()
+            .map(|(name, functions)| (name, functions))
+            .chain(pending.iter())
+            .collect::<Vec<_>>();
+        for &(name, functions) in &sets {
+            // Sets named with `overload_set` group functions and methods on purpose.
+            if functions.iter().any(|data| data.grouped) {
+                continue;
+            }
+            let free = functions
+                .iter()
+                .find(|data| data.arg_type == ArgType::Other);
+            let method = functions
+                .iter()
+                .find(|data| data.arg_type != ArgType::Other);
+            if let (Some(free), Some(method)) = (free, method) {
+                problems.push(format!(
+                    "`{}!` mixes the free function at {} with the method at {}, whose macro names collide",
+                    name, free.location, method.location
+                ));
+            }
+        }
+
+        let registered = sets
+            .iter()
+            .flat_map(|(_, functions)| functions.iter())
+            .collect::<Vec<_>>();
+        for &(name, functions) in &sets {
+            // The arity options added by `into` are meant to give way to explicit overloads.
+            for data in functions
+                .iter()
+                .filter(|data| data.convert != Convert::Pack)
+            {
+                let shadowing = functions
+                    .iter()
+                    .find(|other| other.priority > data.priority && same_slot(other, data));
+                if let Some(other) = shadowing {
+                    let option = match other.convert {
+                        Convert::Pack => format!("the `into` option of `{}`", other.name),
+                        _ => format!("`{}`", other.name),
+                    };
+                    problems.push(format!(
+                        "`{}!` never calls `{}` at {}: {} at {} takes the same arguments with a higher priority",
+                        name, data.name, data.location, option, other.location
+                    ));
+                }
+            }
+        }
+        let mut reported = Vec::new();
+        for data in &registered {
+            for sibling in &data.self_calls {
+                let exists = registered.iter().any(|other| {
+                    other.name == *sibling && owner(&other.arg_type) == owner(&data.arg_type)
+                });
+                if !exists && !reported.contains(&(&data.location, sibling)) {
+                    reported.push((&data.location, sibling));
+                    problems.push(format!(
+                        "`{}` at {} calls `{}` through `self_overload!`, but no overload of that arity is registered",
+                        data.name, data.location, sibling
+                    ));
+                }
+            }
+        }
+        for &(name, functions) in &sets {
+            // The overloads of a set share their original name, unless `overload_set` or an alias grouped them.
+            let mut kept = functions
+                .iter()
+                .filter(|data| data.alias && !data.grouped && data.convert != Convert::Pack);
+            if let (Some(first), Some(other)) = (kept.next(), kept.next()) {
+                problems.push(format!(
+                    "the overloads of `{}!` at {} and {} both keep their original name with `default`, whose \
+                     functions collide",
+                    name, first.location, other.location
+                ));
+            }
+        }
+        problems
+    })
+}
+
+/// Type whose impl holds an overload, `None` for free functions.
+fn owner(arg_type: &ArgType) -> Option<&str> {
+    match arg_type {
+        ArgType::Struct(ty) | ArgType::Instance(ty) | ArgType::Trait(ty, _) => Some(ty),
+        ArgType::Other => None,
+    }
+}
+
+/// Markdown summary of the sets generated so far, written to the directory in `OVERFN_REPORT` by
+/// [`macros!()`](macro@crate::macros).
+pub(crate) fn report() -> String {
+    with_registry(|registry| markdown(&registry.generated))
+}
+
+/// Markdown table of the overloads of each of `sets`, under a heading named after its macro.
+fn markdown(sets: &[(String, Vec<FunctionData>)]) -> String {
+    let mut report = String::from("# Overload sets\n");
+    for (name, functions) in sets {
+        report.push_str(&format!(
+            "\n## `{}!`\n\n| Function | Arguments | Receiver | Defined at |\n| --- | --- | --- | --- |\n",
+            name
+        ));
+        for data in functions {
+            let receiver = match data.receiver {
+                Some(ReceiverKind::Value) => "`self`",
+                Some(ReceiverKind::Ref) => "`&self`",
+                Some(ReceiverKind::Mut) => "`&mut self`",
+                None => "",
+            };
+            report.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                data.name, data.n_args, receiver, data.location
+            ));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overload(
+        name: &str,
+        n_args: usize,
+        receiver: Option<ReceiverKind>,
+        line: usize,
+    ) -> FunctionData {
+        FunctionData {
+            name: name.to_string(),
+            n_args,
+            receiver,
+            location: Location {
+                file: "src/lib.rs".to_string(),
+                line,
+                column: 1,
+            },
+            ..FunctionData::default()
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let sets = vec![
+            (
+                "add".to_string(),
+                vec![overload("add_1", 1, None, 3), overload("add_2", 2, None, 8)],
+            ),
+            (
+                "Test_len".to_string(),
+                vec![overload("len_0", 0, Some(ReceiverKind::Ref), 15)],
+            ),
+        ];
+        assert_eq!(
+            markdown(&sets),
+            "# Overload sets\n\
+            \n\
+            ## `add!`\n\
+            \n\
+            | Function | Arguments | Receiver | Defined at |\n\
+            | --- | --- | --- | --- |\n\
+            | `add_1` | 1 |  | src/lib.rs:3:1 |\n\
+            | `add_2` | 2 |  | src/lib.rs:8:1 |\n\
+            \n\
+            ## `Test_len!`\n\
+            \n\
+            | Function | Arguments | Receiver | Defined at |\n\
+            | --- | --- | --- | --- |\n\
+            | `len_0` | 0 | `&self` | src/lib.rs:15:1 |\n"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg_attr(feature = "fn_traits", feature(fn_traits, unboxed_closures))]
+use overfn::*;
+
+config!(mangle = "__{name}_ov{arity}", inline);
+
+#[overload]
+fn parse(text: &str) -> usize {
+    text.len()
+}
+
+#[overload]
+fn parse(text: &str, radix: u32) -> u32 {
+    u32::from_str_radix(text, radix).unwrap()
+}
+
+fn parse_2(left: usize, right: usize) -> usize {
+    left + right
+}
+
+#[overload(mangle = hidden)]
+fn hidden(item: usize) -> usize {
+    item
+}
+
+#[overload(op = Add)]
+fn plus(left: Meters, right: Meters) -> Meters {
+    Meters(left.0 + right.0)
+}
+
+#[derive(Debug, PartialEq)]
+struct Meters(u32);
+
+impl Meters {
+    #[overload(Meters)]
+    fn new(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+macros!();
+
+#[test]
+fn test_mangling() {
+    assert_eq!(parse!("abc"), 3);
+    assert_eq!(parse!("ff", 16), 255);
+    assert_eq!(__parse_ov1("abcd"), 4);
+    assert_eq!(parse_2(1, 2), 3);
+    assert_eq!(__overfn_hidden_ov1(2), 2);
+    assert_eq!(Meters::__new_ov1(3), Meters(3));
+    assert_eq!(Meters_new!(1) + Meters_new!(2), Meters(3));
+    assert_eq!(__plus_ov2_meters_meters(Meters(1), Meters(1)), Meters(2));
+}
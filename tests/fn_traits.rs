@@ -0,0 +1,48 @@
+#![cfg(feature = "fn_traits")]
+#![feature(fn_traits, unboxed_closures)]
+use overfn::*;
+
+#[overload]
+fn add(item: usize) -> usize {
+    item + 10
+}
+
+#[overload]
+fn add(left: usize, right: usize) -> usize {
+    left + right
+}
+
+struct Test(usize);
+
+impl Test {
+    #[overload(Test)]
+    fn new() -> Self {
+        Self(0)
+    }
+
+    #[overload(Test)]
+    fn new(item: usize) -> Self {
+        Self(item)
+    }
+}
+
+macros!();
+
+fn apply(f: impl Fn(usize, usize) -> usize) -> usize {
+    f(3, 4)
+}
+
+#[test]
+fn test_call_syntax() {
+    assert_eq!(add(2), 12);
+    assert_eq!(add(2, 3), 5);
+    assert_eq!(add!(2, 3), 5);
+    assert_eq!(Test_new().0, 0);
+    assert_eq!(Test_new(2).0, 2);
+}
+
+#[test]
+fn test_as_value() {
+    assert_eq!(apply(add), 7);
+    assert_eq!([1, 2].map(add), [11, 12]);
+}
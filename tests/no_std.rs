@@ -0,0 +1,64 @@
+#![cfg_attr(feature = "fn_traits", feature(fn_traits, unboxed_closures))]
+#![no_std]
+use overfn::*;
+
+#[overload]
+fn scale(value: u32) -> u32 {
+    value * 2
+}
+
+#[overload]
+fn scale(value: u32, factor: u32) -> u32 {
+    value * factor
+}
+
+struct Pin(u8);
+
+impl Pin {
+    #[overload(Pin, ctor)]
+    fn new() -> Self {
+        Pin(0)
+    }
+
+    #[overload(Pin)]
+    fn new(number: u8) -> Self {
+        Pin(number)
+    }
+
+    #[overload(Pin)]
+    fn toggle(&mut self) {
+        self.0 ^= 1;
+    }
+
+    #[overload(Pin)]
+    fn toggle(&mut self, mask: u8) {
+        self.0 ^= mask;
+    }
+}
+
+macros!();
+
+#[test]
+fn test_without_std() {
+    assert_eq!(scale!(3), 6);
+    assert_eq!(scale!(3, 3), 9);
+    let mut pin = Pin!(4);
+    Pin_toggle!(pin);
+    Pin_toggle!(pin, 4);
+    assert_eq!(pin.0, 1);
+    assert_eq!(Pin_new!().0, 0);
+}
+
+#[cfg(feature = "runtime")]
+#[test]
+fn test_runtime_without_alloc() {
+    assert_eq!(dispatch_scale([5u32]), Ok(10));
+    assert_eq!(dispatch_scale([5u32, 3]), Ok(15));
+    assert_eq!(
+        dispatch_scale::<u32, _>([]),
+        Err(ScaleDispatchError::UnknownArity {
+            given: 0,
+            available: &[1, 2]
+        })
+    );
+}
@@ -0,0 +1,118 @@
+#![cfg_attr(feature = "fn_traits", feature(fn_traits, unboxed_closures))]
+#![cfg(feature = "runtime")]
+use overfn::*;
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Int(i64),
+    Text(String),
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl TryFrom<Value> for i64 {
+    type Error = ();
+
+    fn try_from(value: Value) -> Result<Self, ()> {
+        match value {
+            Value::Int(value) => Ok(value),
+            Value::Text(_) => Err(()),
+        }
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = ();
+
+    fn try_from(value: Value) -> Result<Self, ()> {
+        match value {
+            Value::Text(value) => Ok(value),
+            Value::Int(_) => Err(()),
+        }
+    }
+}
+
+#[overload]
+fn repeat(text: String) -> String {
+    text
+}
+
+#[overload]
+fn repeat(text: String, times: i64) -> String {
+    text.repeat(times as usize)
+}
+
+struct Counter;
+
+impl Counter {
+    #[overload(Counter)]
+    fn start() -> i64 {
+        0
+    }
+
+    #[overload(Counter)]
+    fn start(from: i64) -> i64 {
+        from
+    }
+}
+
+macros!();
+
+#[test]
+fn test_dispatch_values() {
+    let text = || Value::Text("ab".to_string());
+    assert_eq!(dispatch_repeat(vec![text()]), Ok(text()));
+    assert_eq!(
+        dispatch_repeat(vec![text(), Value::Int(2)]),
+        Ok(Value::Text("abab".to_string()))
+    );
+    assert_eq!(dispatch_Counter_start(vec![]), Ok(Value::Int(0)));
+    assert_eq!(
+        dispatch_Counter_start(vec![Value::Int(4)]),
+        Ok(Value::Int(4))
+    );
+}
+
+#[test]
+fn test_dispatch_errors() {
+    assert_eq!(
+        dispatch_repeat(Vec::<Value>::new()),
+        Err(RepeatDispatchError::UnknownArity {
+            given: 0,
+            available: &[1, 2]
+        })
+    );
+    assert_eq!(
+        dispatch_repeat(vec![Value::Int(1), Value::Int(2)]),
+        Err(RepeatDispatchError::TypeMismatch { index: 0 })
+    );
+    assert_eq!(
+        dispatch_Counter_start(vec![Value::Text(String::new())]),
+        Err(CounterStartDispatchError::TypeMismatch { index: 0 })
+    );
+}
+
+#[test]
+fn test_dispatch_error_messages() {
+    let error: Box<dyn std::error::Error> =
+        Box::new(dispatch_repeat(Vec::<Value>::new()).unwrap_err());
+    assert_eq!(
+        error.to_string(),
+        "no overload of `repeat!` takes 0 values, only [1, 2]"
+    );
+    let error = dispatch_repeat(vec![Value::Int(1)]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "value 0 doesn't convert to the type of its argument of `repeat!`"
+    );
+}
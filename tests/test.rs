@@ -1,3 +1,4 @@
+#![cfg_attr(feature = "fn_traits", feature(fn_traits, unboxed_closures))]
 use overfn::*;
 
 #[overload]
@@ -15,6 +16,10 @@ fn add(left: usize, right: usize, other: usize) -> usize {
     left + right + other
 }
 
+fn add_before_macros() -> usize {
+    add!(1, 1)
+}
+
 macros!();
 
 #[test]
@@ -29,6 +34,43 @@ fn it_works() {
     assert_eq!(result, 6);
 }
 
+#[test]
+fn test_spread() {
+    assert_eq!(add!(..[2]), 12);
+    assert_eq!(add!(..[2, 2]), 4);
+    assert_eq!(add!(..(2, 2, 2,)), 6);
+
+    let pair = (2, 3);
+    assert_eq!(add!(..pair), 5);
+
+    let triple = [1, 2, 3];
+    assert_eq!(add!(..triple), 6);
+}
+
+#[test]
+fn test_spread_method() {
+    let test = Test_new!(..(2, 2));
+    assert_eq!(Test_test!(test, ..[2]), 6);
+
+    let args = (1, 1);
+    let test = Test_new!(..args);
+    assert_eq!(Test_test!(test), 2);
+}
+
+#[test]
+fn test_call_before_macros() {
+    assert_eq!(add_before_macros(), 2);
+}
+
+mod nested {
+    use super::add_2;
+
+    #[test]
+    fn test_call_by_path() {
+        assert_eq!(crate::add!(1, 2), 3);
+    }
+}
+
 struct Test {
     a: usize,
     b: usize,
@@ -78,3 +120,2246 @@ fn test_object_method() {
     let result = Test_test!(test, 2);
     assert_eq!(result, 6);
 }
+
+mod first {
+    use overfn::*;
+
+    #[overload]
+    pub fn load(left: usize, right: usize) -> usize {
+        left + right
+    }
+}
+
+mod second {
+    use overfn::*;
+
+    #[overload(override)]
+    pub fn load(left: usize, right: usize) -> usize {
+        left * right
+    }
+}
+
+use second::load_2;
+
+macros!();
+
+#[test]
+fn test_override() {
+    assert_eq!(first::load_2(2, 3), 5);
+    assert_eq!(load!(2, 3), 6);
+}
+
+/// Sums the given values.
+#[overload(doc)]
+pub fn sum(item: usize) -> usize {
+    item
+}
+
+#[overload(doc)]
+pub fn sum(left: usize, right: usize) -> usize {
+    left + right
+}
+
+macros!();
+
+#[test]
+fn test_public_overloads() {
+    assert_eq!(sum!(2), 2);
+    assert_eq!(sum!(2, 3), 5);
+}
+
+#[overload(set)]
+fn mul(item: usize) -> usize {
+    item
+}
+
+#[overload(set = mul)]
+fn product(left: usize, right: usize) -> usize {
+    left * right
+}
+
+struct Counter(usize);
+
+impl Counter {
+    #[overload(Counter, set)]
+    fn new() -> Self {
+        Self(0)
+    }
+
+    #[overload(Counter, set)]
+    fn new(start: usize) -> Self {
+        Self(start)
+    }
+
+    #[overload(Counter, set)]
+    fn get(&self) -> usize {
+        self.0
+    }
+
+    #[overload(Counter, set)]
+    fn get(&self, offset: usize) -> usize {
+        self.0 + offset
+    }
+}
+
+define_overloads!(mul);
+define_overloads!(Counter::new);
+define_overloads!(Counter.get);
+
+#[test]
+fn test_stateless_sets() {
+    assert_eq!(mul!(3), 3);
+    assert_eq!(mul!(3, 4), 12);
+
+    let counter = Counter_new!();
+    assert_eq!(Counter_get!(counter), 0);
+
+    let counter = Counter_new!(2);
+    assert_eq!(Counter_get!(counter), 2);
+    assert_eq!(Counter_get!(counter, 3), 5);
+}
+
+struct Point {
+    x: usize,
+    y: usize,
+}
+
+impl Point {
+    #[overload(Point, skip_self)]
+    fn sum(&self) -> usize {
+        self.x + self.y
+    }
+
+    #[overload(Point, skip_self)]
+    fn sum(&self, other: usize) -> usize {
+        self.x + self.y + other
+    }
+
+    #[overload(Point, set, skip_self)]
+    fn scale(&self, factor: usize) -> usize {
+        (self.x + self.y) * factor
+    }
+}
+
+macros!();
+define_overloads!(Point.scale, skip_self);
+
+#[test]
+fn test_skip_self() {
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(point.sum_0(), 3);
+    assert_eq!(point.sum_1(1), 4);
+    assert_eq!(Point_sum!(point), 3);
+    assert_eq!(Point_sum!(point, 1), 4);
+    assert_eq!(Point_scale!(point, 2), 6);
+}
+
+#[overload]
+pub(crate) fn max(item: usize) -> usize {
+    item
+}
+
+#[overload(inherit_vis)]
+pub fn max(left: usize, right: usize) -> usize {
+    left.max(right)
+}
+
+macros!();
+
+#[test]
+fn test_inherit_vis() {
+    assert_eq!(max!(2), 2);
+    assert_eq!(max!(2, 3), 3);
+}
+
+struct Money(usize);
+
+impl From<usize> for Money {
+    #[overload(Money as From<usize>)]
+    fn from(cents: usize) -> Self {
+        Money(cents)
+    }
+}
+
+trait FromParts {
+    fn from(units: usize, cents: usize) -> Self;
+}
+
+impl FromParts for Money {
+    #[overload(Money as FromParts)]
+    fn from(units: usize, cents: usize) -> Self {
+        Money(units * 100 + cents)
+    }
+}
+
+trait Cents {
+    fn cents(&self) -> usize;
+}
+
+impl Cents for Money {
+    #[overload(Money as Cents)]
+    fn cents(&self) -> usize {
+        self.0
+    }
+}
+
+macros!();
+
+#[test]
+fn test_trait_impls() {
+    assert_eq!(Money_from!(250).0, 250);
+    assert_eq!(Money_from!(2, 50).0, 250);
+
+    let money = Money_from!(2, 50);
+    assert_eq!(Money_cents!(money), 250);
+    assert_eq!(Money_cents!(money), 250);
+}
+
+#[derive(Debug, PartialEq)]
+struct Config {
+    host: String,
+    port: u16,
+}
+
+impl From<&str> for Config {
+    fn from(host: &str) -> Self {
+        Config {
+            host: host.to_string(),
+            port: 80,
+        }
+    }
+}
+
+impl From<(&str, u16)> for Config {
+    fn from((host, port): (&str, u16)) -> Self {
+        Config {
+            host: host.to_string(),
+            port,
+        }
+    }
+}
+
+#[overload(into(2))]
+fn connect(config: Config) -> String {
+    format!("{}:{}", config.host, config.port)
+}
+
+#[overload(into)]
+fn distance(from: u64, to: u64) -> u64 {
+    to - from
+}
+
+macros!();
+
+#[test]
+fn test_into() {
+    assert_eq!(connect!("localhost"), "localhost:80");
+    assert_eq!(connect!("localhost", 8080), "localhost:8080");
+    assert_eq!(
+        connect!(Config {
+            host: "127.0.0.1".to_string(),
+            port: 443
+        }),
+        "127.0.0.1:443"
+    );
+    assert_eq!(distance!(2u8, 10u32), 8);
+}
+
+#[overload]
+fn early(value: usize) -> usize {
+    value
+}
+
+#[overload]
+fn late(value: usize) -> usize {
+    value * 2
+}
+
+macros!(only(early));
+
+#[test]
+fn test_only() {
+    assert_eq!(early!(3), 3);
+}
+
+#[overload]
+fn late(left: usize, right: usize) -> usize {
+    left * right
+}
+
+#[overload]
+fn skipped(value: usize) -> usize {
+    value
+}
+
+macros!(except(skipped));
+
+#[test]
+fn test_except() {
+    assert_eq!(late!(3), 6);
+    assert_eq!(late!(3, 4), 12);
+}
+
+macros!();
+
+#[test]
+fn test_leftover() {
+    assert_eq!(skipped!(5), 5);
+}
+
+#[derive(Default)]
+struct Request {
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    #[overload(Request, chain)]
+    fn with_header(&mut self, key: &str, value: &str) {
+        self.headers.push((key.to_string(), value.to_string()));
+    }
+
+    #[overload(Request, chain)]
+    fn with_header(&mut self, (key, value): (&str, &str)) {
+        self.with_header_3(key, value);
+    }
+
+    fn len(&self) -> usize {
+        self.headers.len()
+    }
+}
+
+macros!();
+
+#[test]
+fn test_chain() {
+    let mut request = Request::default();
+    assert_eq!(Request_with_header!(request, "a", "1").len(), 1);
+    let request = Request_with_header!(Request_with_header!(request, ("b", "2")), "c", "3");
+    assert_eq!(request.len(), 3);
+    assert_eq!(request.headers[1], ("b".to_string(), "2".to_string()));
+}
+
+#[overload(export)]
+pub fn exported(value: usize) -> usize {
+    value
+}
+
+#[overload(internal)]
+fn exported(left: usize, right: usize) -> usize {
+    left + right
+}
+
+macros!();
+
+#[test]
+fn test_export() {
+    assert_eq!(exported!(1), 1);
+    assert_eq!(exported!(1, 2), 3);
+    assert_eq!(crate::exported!(1), 1);
+    assert_eq!(crate::exported!(..[4]), 4);
+}
+
+#[overload]
+fn log(message: impl std::fmt::Display) -> String {
+    format!("[info] {}", message)
+}
+
+#[overload]
+fn log<T: std::fmt::Debug>(level: u8, message: T) -> String {
+    format!("[{}] {:?}", level, message)
+}
+
+#[overload]
+fn log<K, V>(level: u8, key: K, value: V) -> String
+where
+    K: std::fmt::Display,
+    V: Into<String>,
+{
+    format!("[{}] {}={}", level, key, value.into())
+}
+
+macros!();
+
+#[test]
+fn test_impl_trait_arguments() {
+    assert_eq!(log!("started"), "[info] started");
+    assert_eq!(log!(3.5), "[info] 3.5");
+    assert_eq!(log!(2, "text"), "[2] \"text\"");
+    assert_eq!(log!(2, vec![1, 2]), "[2] [1, 2]");
+    assert_eq!(log!(1, "port", "80"), "[1] port=80");
+    assert_eq!(log!(..("spread",)), "[info] spread");
+}
+
+struct Logger(Vec<String>);
+
+impl Logger {
+    #[overload(Logger)]
+    fn push(&mut self, message: impl ToString) {
+        self.0.push(message.to_string());
+    }
+
+    #[overload(Logger)]
+    fn push(&mut self, messages: impl IntoIterator<Item = impl ToString>, prefix: &str) {
+        for message in messages {
+            self.0.push(format!("{}{}", prefix, message.to_string()));
+        }
+    }
+}
+
+macros!();
+
+#[test]
+fn test_impl_trait_methods() {
+    let mut logger = Logger(Vec::new());
+    Logger_push!(logger, 1);
+    Logger_push!(logger, ["a", "b"], "- ");
+    assert_eq!(logger.0, ["1", "- a", "- b"]);
+}
+
+#[overload(into(2))]
+fn open(config: Config) -> String {
+    format!("config {}:{}", config.host, config.port)
+}
+
+#[overload(priority = 1)]
+fn open(host: &str, port: u16) -> String {
+    format!("explicit {}:{}", host, port)
+}
+
+macros!();
+
+#[test]
+fn test_priority() {
+    assert_eq!(open!("localhost", 8080), "explicit localhost:8080");
+    assert_eq!(open!("localhost"), "config localhost:80");
+}
+
+#[overload(catch_unwind)]
+fn checked(values: &[u32], index: usize) -> u32 {
+    values[index]
+}
+
+#[overload(catch_unwind)]
+fn checked(value: u32) -> u32 {
+    assert!(value > 0, "value must be positive");
+    value
+}
+
+struct Parser;
+
+impl Parser {
+    #[overload(Parser, catch_unwind)]
+    fn new() -> Self {
+        Parser
+    }
+
+    #[overload(Parser, catch_unwind)]
+    fn parse(&self, input: &str) -> u32 {
+        input.parse().unwrap()
+    }
+}
+
+macros!();
+
+#[test]
+fn test_catch_unwind() {
+    assert_eq!(checked!(&[1, 2], 1).unwrap(), 2);
+    assert!(checked!(&[1, 2], 2).is_err());
+    let payload = checked!(0).unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<&str>(),
+        Some(&"value must be positive")
+    );
+    let parser = Parser_new!().unwrap();
+    assert_eq!(Parser_parse!(parser, "12").unwrap(), 12);
+    assert!(Parser_parse!(Parser, "twelve").is_err());
+}
+
+#[derive(Debug, PartialEq)]
+struct Wrapper<T>(T);
+
+impl<T: Default> Wrapper<T> {
+    #[overload(Wrapper, generic)]
+    fn new() -> Self {
+        Wrapper(T::default())
+    }
+
+    #[overload(Wrapper, generic)]
+    fn new(value: T) -> Self {
+        Wrapper(value)
+    }
+}
+
+struct Slot<T>(Option<T>);
+
+impl<T> Slot<T> {
+    #[overload(Slot, set)]
+    fn empty() -> Self {
+        Slot(None)
+    }
+}
+
+macros!();
+define_overloads!(Slot::empty);
+
+#[test]
+fn test_turbofish() {
+    assert_eq!(Wrapper_new!(::<u32>), Wrapper(0));
+    assert_eq!(Wrapper_new!(::<u8>, 5), Wrapper(5u8));
+    assert_eq!(Wrapper_new!(String::from("a")), Wrapper("a".to_string()));
+    assert!(Slot_empty!(::<String>).0.is_none());
+}
+
+#[overload]
+fn gated(value: usize) -> usize {
+    value
+}
+
+#[overload]
+#[cfg(test)]
+fn gated(left: usize, right: usize) -> usize {
+    left + right
+}
+
+#[overload]
+#[cfg(any())]
+fn gated(a: usize, b: usize, c: usize) -> usize {
+    a + b + c
+}
+
+#[overload]
+#[cfg(unix)]
+fn os_family() -> &'static str {
+    "unix"
+}
+
+#[overload]
+#[cfg(not(unix))]
+fn os_family() -> &'static str {
+    "other"
+}
+
+macros!();
+
+#[test]
+fn test_cfg() {
+    assert_eq!(gated!(1), 1);
+    assert_eq!(gated!(1, 2), 3);
+    assert_eq!(gated!(..[1, 2]), 3);
+    assert_eq!(os_family!(), if cfg!(unix) { "unix" } else { "other" });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Vector(i32, i32);
+
+impl Vector {
+    #[overload(Vector, receiver = copy)]
+    fn len(&self) -> i32 {
+        self.0.abs() + self.1.abs()
+    }
+
+    #[overload(Vector, receiver = copy)]
+    fn scale(&mut self, factor: i32) -> Vector {
+        self.0 *= factor;
+        self.1 *= factor;
+        *self
+    }
+}
+
+trait Flip {
+    fn flip(self) -> Self;
+}
+
+impl Flip for Vector {
+    #[overload(Vector as Flip, receiver = copy)]
+    fn flip(self) -> Self {
+        Vector(self.1, self.0)
+    }
+}
+
+macros!();
+
+#[test]
+fn test_copy_receiver() {
+    let vector = Vector(1, -2);
+    assert_eq!(Vector_len!(vector), 3);
+    assert_eq!(Vector_scale!(vector, 2), Vector(2, -4));
+    assert_eq!(vector, Vector(1, -2));
+    assert_eq!(Vector_flip!(vector), Vector(-2, 1));
+    assert_eq!(Vector_len!(vector), 3);
+}
+
+mod routed {
+    use overfn::*;
+
+    #[overload]
+    pub fn scaled(value: usize) -> usize {
+        value * 10
+    }
+
+    macros!();
+}
+
+use routed::scaled_1;
+
+#[test]
+fn test_call() {
+    assert_eq!(call!(add(2)), 12);
+    assert_eq!(call!(add(1, 2)), 3);
+    let test = call!(Test::new(2, 3));
+    assert_eq!(call!(Test::test(test, 1)), 6);
+    assert_eq!(call!(Wrapper::<u8>::new(5)), Wrapper(5u8));
+    assert_eq!(call!(Wrapper::<u32>::new()), Wrapper(0));
+    assert_eq!(call!(routed::scaled(2)), 20);
+}
+
+#[derive(Debug, PartialEq)]
+struct Buffer(Vec<u8>);
+
+impl Buffer {
+    #[overload(Buffer, ext)]
+    fn push(&mut self, byte: u8) {
+        self.0.push(byte);
+    }
+
+    #[overload(Buffer, ext)]
+    fn push(&mut self, byte: u8, count: usize) -> usize {
+        self.0.extend(std::iter::repeat_n(byte, count));
+        self.0.len()
+    }
+
+    #[overload(Buffer, ext)]
+    fn finish(self) -> Self {
+        self
+    }
+}
+
+macros!();
+
+#[test]
+fn test_extension_trait() {
+    let mut buffer = Buffer(Vec::new());
+    buffer.push_ov((1,));
+    assert_eq!(buffer.push_ov((2, 2)), 3);
+    Buffer_push!(buffer, 3);
+    assert_eq!(buffer.finish_ov(()), Buffer(vec![1, 2, 2, 3]));
+}
+
+#[overload(place(0))]
+fn reset(counter: &mut usize) {
+    *counter = 0;
+}
+
+#[overload(place(1))]
+fn reset(start: usize, counter: &mut usize) {
+    *counter = start;
+}
+
+struct Totals(Vec<usize>);
+
+impl Totals {
+    #[overload(Totals, place(0))]
+    fn add_into(&self, target: &mut usize) {
+        *target += self.0.iter().sum::<usize>();
+    }
+}
+
+macros!();
+
+#[test]
+fn test_place() {
+    let mut counter = 5;
+    reset!(counter);
+    assert_eq!(counter, 0);
+    reset!(3, counter);
+    assert_eq!(counter, 3);
+
+    let totals = Totals(vec![1, 2]);
+    let mut pair = (0, 10);
+    Totals_add_into!(totals, pair.1);
+    assert_eq!(pair, (0, 13));
+}
+
+#[overload(by_len)]
+fn digest(data: [u8; 16]) -> usize {
+    data.len()
+}
+
+#[overload(by_len)]
+fn digest(data: [u8; 32]) -> usize {
+    data.len() * 2
+}
+
+#[overload]
+fn digest(data: [u8; 32], rounds: usize) -> usize {
+    data.len() * rounds
+}
+
+macros!();
+
+#[test]
+fn test_by_len() {
+    assert_eq!(digest!([0; 16]), 16);
+    let key = [0u8; 32];
+    assert_eq!(digest!(key), 64);
+    assert_eq!(digest!(key, 3), 96);
+    assert_eq!(digest!(..([0; 16],)), 16);
+}
+
+#[overload_set("size")]
+fn str_size(text: &str) -> usize {
+    text.len()
+}
+
+struct Grid(usize, usize);
+
+impl Grid {
+    #[overload_set("size", Grid)]
+    fn area(&self, scale: usize) -> usize {
+        self.0 * self.1 * scale
+    }
+
+    #[overload_set("size", Grid)]
+    fn cells() -> usize {
+        0
+    }
+}
+
+macros!();
+
+#[test]
+fn test_overload_set() {
+    assert_eq!(size!(), 0);
+    assert_eq!(size!("abc"), 3);
+    assert_eq!(size!(Grid(2, 3), 2), 12);
+}
+
+struct Span(usize, usize);
+
+impl Span {
+    #[overload(Span, dispatch)]
+    fn new() -> Self {
+        Self(0, 0)
+    }
+
+    #[overload(Span)]
+    fn new(end: usize) -> Self {
+        Self(0, end)
+    }
+
+    #[overload(Span)]
+    fn new(start: usize, end: usize) -> Self {
+        Self(start, end)
+    }
+}
+
+macros!();
+
+#[test]
+fn test_dispatch() {
+    let constructors: Vec<SpanNewArgs> = vec![
+        SpanNewArgs::Zero,
+        SpanNewArgs::One(3),
+        SpanNewArgs::Two(1, 4),
+    ];
+    let spans = constructors
+        .into_iter()
+        .map(Span_new_dispatch)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans.iter().map(|span| span.1 - span.0).collect::<Vec<_>>(),
+        [0, 3, 3]
+    );
+}
+
+struct Gauge(usize);
+
+impl Gauge {
+    #[overload(Gauge)]
+    fn raise(&mut self, by: usize) {
+        self.0 += by;
+    }
+}
+
+trait Level {
+    fn level(&self) -> usize;
+    fn lower(&mut self, by: usize);
+}
+
+impl Level for Gauge {
+    #[overload(Gauge as Level)]
+    fn level(&self) -> usize {
+        self.0
+    }
+
+    #[overload(Gauge as Level)]
+    fn lower(&mut self, by: usize) {
+        self.0 -= by;
+    }
+}
+
+macros!();
+
+#[test]
+fn test_receiver_borrows() {
+    let mut gauge = Gauge(0);
+    Gauge_raise!(gauge, 4);
+    Gauge_raise!(&mut gauge, 4);
+    let reborrowed = &mut gauge;
+    Gauge_raise!(reborrowed, 2);
+    Gauge_lower!(reborrowed, 1);
+    Gauge_lower!(&mut gauge, 1);
+    assert_eq!(Gauge_level!(gauge), 8);
+    assert_eq!(Gauge_level!(&&gauge), 8);
+    assert_eq!(Gauge_level!(Box::new(Gauge(3))), 3);
+}
+
+struct Tick(usize);
+
+impl Tick {
+    #[overload(Tick)]
+    fn incr(&mut self) -> usize {
+        Tick_incr!(self, 1)
+    }
+
+    #[overload(Tick)]
+    fn incr(&mut self, by: usize) -> usize {
+        self.0 += by;
+        self.0
+    }
+
+    #[overload(Tick, receiver = method)]
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+}
+
+struct Tally {
+    kept: Tick,
+    dropped: Tick,
+}
+
+impl Tally {
+    fn keep(&mut self) -> usize {
+        Tick_incr!(self.kept)
+    }
+}
+
+macros!();
+
+#[test]
+fn test_mut_receivers() {
+    let tally = &mut Tally {
+        kept: Tick(0),
+        dropped: Tick(0),
+    };
+    assert_eq!(tally.keep(), 1);
+    let kept = &mut tally.kept;
+    assert_eq!(Tick_incr!(tally.dropped, 3), 3);
+    assert_eq!(Tick_incr!(kept), 2);
+    Tick_reset!(tally.dropped);
+    assert_eq!(Tick_incr!(tally.dropped), 1);
+    let mut counters = [Tick(5)];
+    assert_eq!(Tick_incr!(counters[0], 2), 7);
+}
+
+#[test]
+fn test_local_sets() {
+    #[overload(local)]
+    fn halve(value: usize) -> usize {
+        value / 2
+    }
+
+    #[overload(local)]
+    fn halve(left: usize, right: usize) -> usize {
+        (left + right) / 2
+    }
+
+    macros!(local);
+
+    assert_eq!(halve!(8), 4);
+    assert_eq!(halve!(3, 5), 4);
+    assert_eq!(add!(1, 2), 3);
+}
+
+#[test]
+fn test_trailing_comma() {
+    assert_eq!(add!(2,), 12);
+    assert_eq!(add!(1, 2,), 3);
+    let test = Test_new!(1, 2,);
+    assert_eq!(Test_test!(test, 3,), 6);
+    assert_eq!(Wrapper_new!(::<u8>, 5,), Wrapper(5u8));
+    assert_eq!(Counter_new!(4,).0, 4);
+    assert_eq!(digest!([0; 16],), 16);
+}
+
+fn magnify_1(value: usize) -> usize {
+    value
+}
+
+#[overload(mangle = hidden)]
+fn magnify(value: usize) -> usize {
+    value * 100
+}
+
+#[overload(mangle = hidden)]
+fn magnify(value: usize, factor: usize) -> usize {
+    value * factor
+}
+
+impl Counter {
+    fn step_1(&self) -> usize {
+        1
+    }
+
+    #[overload(Counter, mangle = hidden)]
+    fn step(&self) -> usize {
+        self.0
+    }
+}
+
+macros!();
+
+#[test]
+fn test_hidden_names() {
+    assert_eq!(magnify!(2), 200);
+    assert_eq!(magnify!(2, 3), 6);
+    assert_eq!(magnify_1(2), 2);
+    let counter = Counter_new!(7);
+    assert_eq!(Counter_step!(counter), 7);
+    assert_eq!(counter.step_1(), 1);
+}
+
+/// Area of a square.
+#[overload(default)]
+fn area(side: usize) -> usize {
+    side * side
+}
+
+#[overload]
+fn area(width: usize, height: usize) -> usize {
+    width * height
+}
+
+impl Grid {
+    #[overload(Grid, default)]
+    fn cell(&self, index: usize) -> (usize, usize) {
+        (index % self.0, index / self.0)
+    }
+
+    #[overload(Grid)]
+    fn cell(&self, column: usize, row: usize) -> usize {
+        row * self.0 + column
+    }
+}
+
+macros!();
+
+#[test]
+fn test_default_alias() {
+    assert_eq!(area(3), 9);
+    assert_eq!(area!(3), 9);
+    assert_eq!(area!(2, 3), 6);
+    let grid = Grid(4, 4);
+    assert_eq!(grid.cell(5), (1, 1));
+    assert_eq!(Grid_cell!(grid, 1, 1), 5);
+}
+
+#[overload]
+fn format(value: usize) -> String {
+    value.to_string()
+}
+
+#[overload]
+fn format(value: usize, width: usize) -> String {
+    std::format!("{:>1$}", value, width)
+}
+
+macros!(prefix = "ov_");
+
+#[test]
+fn test_prefix() {
+    assert_eq!(ov_format!(7), "7");
+    assert_eq!(ov_format!(7, 3), "  7");
+    assert_eq!(format!("{}", 7), "7");
+}
+
+#[overload]
+unsafe fn peek(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+#[overload(wrap_unsafe)]
+unsafe fn peek(bytes: &[u8], index: usize) -> u8 {
+    *bytes.as_ptr().add(index % bytes.len())
+}
+
+macros!();
+
+#[test]
+fn test_unsafe() {
+    let bytes = [4, 5];
+    assert_eq!(unsafe { peek!(bytes.as_ptr()) }, 4);
+    assert_eq!(peek!(&bytes, 3), 5);
+}
+
+#[overload(by_literal)]
+fn describe(value: u64) -> String {
+    format!("u64 {}", value)
+}
+
+#[overload(by_literal)]
+fn describe(value: &str) -> String {
+    format!("str {}", value)
+}
+
+#[overload(by_literal)]
+fn describe(value: char, count: u8) -> String {
+    value.to_string().repeat(count.into())
+}
+
+#[overload(by_literal)]
+fn describe(value: f32, count: u8) -> String {
+    format!("{:.1$}", value, count.into())
+}
+
+macros!();
+
+#[test]
+fn test_by_literal() {
+    assert_eq!(describe!(2u64), "u64 2");
+    assert_eq!(describe!(2), "u64 2");
+    assert_eq!(describe!("2"), "str 2");
+    assert_eq!(describe!('a', 3), "aaa");
+    assert_eq!(describe!(1.5, 2), "1.50");
+    let value = 4;
+    assert_eq!(describe!(value), "u64 4");
+    assert_eq!(describe_1_str("5"), "str 5");
+}
+
+#[derive(Debug, PartialEq)]
+struct Color(u8, u8, u8);
+
+impl Color {
+    #[overload(Color, derive_default)]
+    fn new() -> Self {
+        Self(0, 0, 0)
+    }
+
+    #[overload(Color, ctor, derive_from)]
+    fn new(gray: u8) -> Self {
+        Self(gray, gray, gray)
+    }
+
+    #[overload(Color)]
+    fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self(red, green, blue)
+    }
+
+    #[overload(Color, derive_from)]
+    fn parse(hex: &str) -> Result<Self, std::num::ParseIntError> {
+        let value = u32::from_str_radix(hex, 16)?;
+        Ok(Self((value >> 16) as u8, (value >> 8) as u8, value as u8))
+    }
+}
+
+macros!();
+
+#[test]
+fn test_ctor() {
+    assert_eq!(Color!(7), Color(7, 7, 7));
+    assert_eq!(Color!(1, 2, 3), Color_new!(1, 2, 3));
+}
+
+#[test]
+fn test_derive_default() {
+    assert_eq!(Color::default(), Color!());
+}
+
+#[test]
+fn test_derive_from() {
+    assert_eq!(Color::from(4), Color(4, 4, 4));
+    assert_eq!(Color::try_from("010203"), Ok(Color(1, 2, 3)));
+    assert!(Color::try_from("xyz").is_err());
+}
+
+mod shapes {
+    use overfn::overload;
+
+    pub struct Square(pub usize);
+
+    impl Square {
+        #[overload(Square, module = shapes)]
+        pub fn new(side: usize) -> Self {
+            Self(side)
+        }
+    }
+
+    #[overload(module = crate::shapes)]
+    pub fn perimeter(square: &Square) -> usize {
+        square.0 * 4
+    }
+
+    #[overload(module = shapes)]
+    pub fn perimeter(width: usize, height: usize) -> usize {
+        (width + height) * 2
+    }
+}
+
+macros!();
+
+mod measure {
+    #[test]
+    fn test_module_paths() {
+        let square = crate::Square_new!(3);
+        assert_eq!(crate::perimeter!(&square), 12);
+        assert_eq!(crate::perimeter!(2, 3), 10);
+    }
+}
+
+#[overload]
+fn init() -> Vec<usize> {
+    Vec::new()
+}
+
+#[overload]
+fn init(capacity: usize) -> Vec<usize> {
+    Vec::with_capacity(capacity)
+}
+
+macros!();
+
+#[test]
+fn test_zero_arity() {
+    assert!(init!().is_empty());
+    assert!(init!(4).capacity() >= 4);
+    assert!(init_0().is_empty());
+}
+
+#[overload]
+#[deprecated(note = "pass the unit")]
+fn weight(grams: u32) -> u32 {
+    grams
+}
+
+#[overload]
+fn weight(amount: u32, unit: &str) -> u32 {
+    match unit {
+        "kg" => amount * 1000,
+        _ => amount,
+    }
+}
+
+macros!();
+
+#[test]
+fn test_deprecated() {
+    #[allow(deprecated)]
+    let grams = weight!(5);
+    assert_eq!(grams, 5);
+    assert_eq!(weight!(2, "kg"), 2000);
+}
+
+#[overload]
+fn join<T: ToString>(first: T, second: T) -> String {
+    format!("{}{}", first.to_string(), second.to_string())
+}
+
+#[overload]
+fn join<T: ToString>(first: T, second: T, third: T) -> String {
+    format!("{}{}", join!(first, second), third.to_string())
+}
+
+struct Joiner(&'static str);
+
+impl Joiner {
+    #[overload(Joiner)]
+    fn join(&self, first: &str, second: &str) -> String {
+        format!("{}{}{}", first, self.0, second)
+    }
+}
+
+macros!();
+
+#[test]
+fn test_splat() {
+    let pair = (1, 2);
+    assert_eq!(join!(@splat(2) pair), "12");
+    assert_eq!(join!(@splat(3) ('a', 'b', 'c')), "abc");
+    let joiner = Joiner("-");
+    assert_eq!(Joiner_join!(joiner, @splat(2) ("a", "b")), "a-b");
+}
+
+#[test]
+fn test_partial() {
+    let totals = [1, 2].into_iter().map(join!(0, _)).collect::<Vec<_>>();
+    assert_eq!(totals, ["01", "02"]);
+    let wrap = join!('[', _, ']');
+    assert_eq!(wrap('x'), "[x]");
+    let joiners = [Joiner("+"), Joiner("*")];
+    let joined = joiners.iter().map(Joiner_join!(_, "a", "b"));
+    assert_eq!(joined.collect::<Vec<_>>(), ["a+b", "a*b"]);
+    let join = Joiner_join!(_, "a", _);
+    assert_eq!(join(&Joiner("/"), "b"), "a/b");
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Vec2(i32, i32);
+
+#[overload(op = Add)]
+fn plus(a: Vec2, b: Vec2) -> Vec2 {
+    Vec2(a.0 + b.0, a.1 + b.1)
+}
+
+#[overload(op = Add)]
+fn plus(a: Vec2, b: i32) -> Vec2 {
+    Vec2(a.0 + b, a.1 + b)
+}
+
+#[overload(op = BitXor)]
+fn cross(a: &Vec2, b: &Vec2) -> i32 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+#[overload]
+fn plus(a: Vec2, b: i32, c: i32) -> Vec2 {
+    Vec2(a.0 + b, a.1 + c)
+}
+
+macros!();
+
+#[test]
+fn test_op() {
+    assert_eq!(Vec2(1, 2) + Vec2(3, 4), Vec2(4, 6));
+    assert_eq!(Vec2(1, 2) + 1, plus!(Vec2(1, 2), 1));
+    assert_eq!(&Vec2(1, 2) ^ &Vec2(3, 4), cross!(&Vec2(1, 2), &Vec2(3, 4)));
+    assert_eq!(plus!(Vec2(1, 2), 1, 2), Vec2(2, 4));
+}
+
+struct Meter(u32);
+
+impl Meter {
+    #[overload(Meter)]
+    fn read(&self) -> u32 {
+        self.0
+    }
+
+    #[overload(Meter)]
+    fn read(&self, scale: u32) -> u32 {
+        self.0 * scale
+    }
+
+    #[overload(Meter, receiver = method)]
+    fn bump(&mut self, by: u32) -> &mut Self {
+        self.0 += by;
+        self
+    }
+}
+
+fn meter(value: u32) -> Option<Box<Meter>> {
+    Some(Box::new(Meter(value)))
+}
+
+macros!();
+
+#[test]
+fn test_qualified_receiver() {
+    let read = || Some(Meter_read!(meter(3)?, Meter_read!(meter(2)?)));
+    assert_eq!(read(), Some(6));
+    let mut meter = Meter(1);
+    let bumped = &mut meter;
+    assert_eq!(Meter_read!(Meter_bump!(bumped, 2)), 3);
+}
+
+async fn fetch_meter(value: u32) -> Meter {
+    Meter(value)
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    loop {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn test_receiver_expressions() {
+    let read = || Some(Meter_read!(*meter(4)?, 2));
+    assert_eq!(read(), Some(8));
+    assert_eq!(block_on(async { Meter_read!(fetch_meter(2).await, 3) }), 6);
+
+    let mut value = Meter(1);
+    assert_eq!(Meter_read!(&value), 1);
+    assert_eq!(Meter_read!(&&value, 2), 2);
+    assert_eq!(Meter_read!(&value as &Meter, 3), 3);
+    assert_eq!(Meter_bump!(&mut value as &mut Meter, 1).0, 2);
+    assert_eq!(Meter_bump!(&mut value, 1).0, 3);
+    let boxed = Box::new(Meter(5));
+    assert_eq!(Meter_read!(*boxed), 5);
+    let meters = [Meter(6), Meter(7)];
+    assert_eq!(Meter_read!(meters[1], 2), 14);
+}
+
+#[derive(Debug, PartialEq)]
+enum Shape {
+    Circle(i32, i32, u32),
+    Rect(u32, u32),
+}
+
+impl Shape {
+    #[overload(Shape)]
+    fn Circle(radius: u32) -> Self {
+        Self::Circle(0, 0, radius)
+    }
+
+    #[overload(Shape)]
+    fn Circle(x: i32, y: i32, radius: u32) -> Self {
+        Self::Circle(x, y, radius)
+    }
+
+    #[overload(Shape)]
+    fn Rect(side: u32) -> Self {
+        Self::Rect(side, side)
+    }
+
+    #[overload(Shape)]
+    fn area(&self) -> u32 {
+        match self {
+            Self::Circle(_, _, radius) => 3 * radius * radius,
+            Self::Rect(width, height) => width * height,
+        }
+    }
+
+    #[overload(Shape)]
+    fn area(&self, scale: u32) -> u32 {
+        Shape_area!(self) * scale
+    }
+}
+
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+impl Bits {
+    #[overload(Bits)]
+    fn new(int: u32) -> Self {
+        Self { int }
+    }
+
+    #[overload(Bits)]
+    fn new(float: f32, _unit: ()) -> Self {
+        Self { float }
+    }
+
+    #[overload(Bits)]
+    fn int(&self) -> u32 {
+        unsafe { self.int }
+    }
+}
+
+macros!();
+
+#[test]
+fn test_enum() {
+    assert_eq!(Shape_Circle!(2), Shape::Circle(0, 0, 2));
+    assert_eq!(Shape_Circle!(1, 2, 3), Shape::Circle(1, 2, 3));
+    assert_eq!(Shape_area!(Shape_Rect!(2), 3), 12);
+    assert_eq!(Shape_area!(Shape::Circle(1, 1, 1)), 3);
+}
+
+#[test]
+fn test_union() {
+    assert_eq!(Bits_int!(Bits_new!(7)), 7);
+    assert_eq!(Bits_int!(Bits_new!(1.0, ())), 1.0f32.to_bits());
+}
+
+#[overload]
+fn fact(n: u64) -> u64 {
+    fact!(n, 1)
+}
+
+#[overload]
+fn fact(n: u64, acc: u64) -> u64 {
+    match n {
+        0 | 1 => acc,
+        _ => fact!(n - 1, acc * n),
+    }
+}
+
+#[overload]
+fn even(n: u32) -> bool {
+    n == 0 || odd!(n - 1)
+}
+
+#[overload]
+fn odd(n: u32) -> bool {
+    n != 0 && even!(n - 1)
+}
+
+macros!();
+
+#[test]
+fn test_recursion() {
+    assert_eq!(fact!(5), 120);
+    assert!(even!(4));
+    assert!(odd!(7));
+}
+
+#[overload]
+fn checksum(bytes: &[u8]) -> u8 {
+    #![allow(clippy::needless_range_loop)]
+    const SEED: u8 = 7;
+    let mut total = SEED;
+    for i in 0..bytes.len() {
+        total = total.wrapping_add(bytes[i]);
+    }
+    total
+}
+
+#[overload]
+fn checksum(bytes: &[u8], seed: u8) -> u8 {
+    bytes
+        .iter()
+        .fold(seed, |total, byte| total.wrapping_add(*byte))
+}
+
+macros!();
+
+#[test]
+fn test_body() {
+    assert_eq!(checksum!(&[1, 2, 3]), 13);
+    assert_eq!(checksum!(&[1, 2, 3], 0), 6);
+}
+
+#[overload(extern_name = "overfn_stretch")]
+fn stretch(value: f64) -> f64 {
+    value * 2.0
+}
+
+#[overload(extern_name = "overfn_stretch")]
+fn stretch(value: f64, factor: f64) -> f64 {
+    value * factor
+}
+
+#[repr(C)]
+struct Celsius(f64);
+
+impl Celsius {
+    #[overload(Celsius, extern_name = "overfn_celsius_new")]
+    fn new(degrees: f64) -> Self {
+        Self(degrees)
+    }
+}
+
+macros!();
+
+mod c_header {
+    extern "C" {
+        pub fn overfn_stretch_2(value: f64, factor: f64) -> f64;
+    }
+}
+
+#[test]
+fn test_extern_name() {
+    assert_eq!(stretch!(1.5), 3.0);
+    assert_eq!(overfn_stretch_1(1.5), 3.0);
+    assert_eq!(unsafe { c_header::overfn_stretch_2(1.5, 3.0) }, 4.5);
+    assert_eq!(Celsius::overfn_celsius_new_1(20.0).0, 20.0);
+    assert_eq!(Celsius_new!(21.0).0, 21.0);
+}
+
+#[overload(table)]
+fn render() -> String {
+    String::from("nothing")
+}
+
+#[overload]
+fn render(value: i64) -> i64 {
+    value * 2
+}
+
+#[overload]
+fn render(left: &str, right: &str) -> String {
+    format!("{}{}", left, right)
+}
+
+macros!();
+
+#[test]
+fn test_table() {
+    let arities = RENDER_OVERLOADS
+        .iter()
+        .map(|(arity, _)| *arity)
+        .collect::<Vec<_>>();
+    assert_eq!(arities, [0, 1, 2]);
+    for (_, overload) in RENDER_OVERLOADS {
+        match overload {
+            RenderOverload::Zero(call) => assert_eq!(call(), "nothing"),
+            RenderOverload::One(call) => assert_eq!(call(4), 8),
+            RenderOverload::Two(call) => assert_eq!(call("a", "b"), "ab"),
+        }
+    }
+}
+
+/// Captions a number.
+#[overload]
+fn caption(value: i64) -> String {
+    format!("number {}", value)
+}
+
+#[overload]
+fn caption(name: &str, values: &[i64]) -> String {
+    format!("{} of {}", name, values.len())
+}
+
+macros!(docs = "pages", only(caption));
+
+#[test]
+fn test_doc_page() {
+    assert_eq!(caption!(1), "number 1");
+    assert_eq!(caption!("list", &[1, 2]), "list of 2");
+}
+
+#[test]
+fn test_overloads_of() {
+    let arities = overloads_of!(render).map(|(arity, _)| arity);
+    assert_eq!(arities, [0, 1, 2]);
+    assert_eq!(
+        overloads_of!(Tick_incr),
+        [(1, "Tick::incr_1"), (2, "Tick::incr_2")]
+    );
+}
+
+mod shades {
+    use overfn::*;
+
+    #[overload(module = shades)]
+    pub(super) fn shade(level: u8) -> u8 {
+        level / 2
+    }
+
+    #[overload(module = shades)]
+    pub(crate) fn shade(level: u8, by: u8) -> u8 {
+        level / by
+    }
+
+    macros!(vis = pub(crate), only(shade));
+}
+
+#[test]
+fn test_macro_vis() {
+    assert_eq!(shades::shade!(8), 4);
+    assert_eq!(crate::shades::shade!(9, 3), 3);
+}
+
+/// Formats an address.
+#[overload(optional_from = 1)]
+fn address(host: &str, port: u16, scheme: Option<&str>) -> String {
+    format!("{}://{}:{}", scheme.unwrap_or("tcp"), host, port)
+}
+
+impl Tick {
+    #[overload(Tick, optional_from = 0)]
+    fn wind(&mut self, turns: usize, mut step: usize) -> usize {
+        step = step.max(1);
+        Tick_incr!(self, turns * step)
+    }
+}
+
+macros!();
+
+#[test]
+fn test_optional_from() {
+    assert_eq!(address!("localhost"), "tcp://localhost:0");
+    assert_eq!(address!("localhost", 80), "tcp://localhost:80");
+    assert_eq!(
+        address!("localhost", 80, Some("http")),
+        "http://localhost:80"
+    );
+    assert_eq!(address_1("host"), address_3("host", 0, None));
+    let mut tick = Tick(0);
+    assert_eq!(Tick_wind!(tick), 0);
+    assert_eq!(Tick_wind!(tick, 2), 2);
+    assert_eq!(Tick_wind!(tick, 2, 3), 8);
+}
+
+macro_rules! scaled {
+    ($name:ident, $factor:expr) => {
+        #[overload]
+        fn $name(value: u32) -> u32 {
+            value * $factor
+        }
+
+        #[overload(optional_from = 2)]
+        fn $name(value: u32, offset: u32, extra: u32) -> u32 {
+            value * $factor + offset + extra
+        }
+    };
+}
+
+scaled!(tripled, 3);
+
+macro_rules! tapering {
+    () => {
+        #[overload(default)]
+        fn taper(value: u32) -> u32 {
+            value / 2
+        }
+
+        #[overload]
+        fn taper(value: u32, extra: u32) -> u32 {
+            value / 2 + extra
+        }
+
+        macros!(only(taper));
+    };
+}
+
+tapering!();
+
+macros!(only(tripled));
+
+#[test]
+fn test_macro_generated() {
+    assert_eq!(tripled!(2), 6);
+    assert_eq!(tripled!(2, 1), 7);
+    assert_eq!(tripled!(2, 1, 1), 8);
+    assert_eq!(tripled_1(2), 6);
+    assert_eq!(taper!(4), 2);
+    assert_eq!(taper!(4, 1), 3);
+    assert_eq!(taper!(_, 1)(6), 4);
+    assert_eq!(taper(4), taper_1(4));
+}
+
+mod json {
+    use overfn::overload;
+
+    #[overload(module = json)]
+    pub fn decode(text: &str) -> Vec<String> {
+        text.trim_matches(['[', ']'])
+            .split(',')
+            .map(String::from)
+            .collect()
+    }
+
+    #[overload(module = json)]
+    pub fn decode(text: &str, limit: usize) -> Vec<String> {
+        decode_1(text).into_iter().take(limit).collect()
+    }
+}
+
+mod csv {
+    use overfn::overload;
+
+    #[overload(module = csv)]
+    pub fn decode(text: &str) -> Vec<String> {
+        text.split(';').map(String::from).collect()
+    }
+}
+
+macros!(module = json);
+macros!(module = crate::csv);
+
+mod formats {
+    #[test]
+    fn test_module_sets() {
+        assert_eq!(crate::json_decode!("[a,b]"), ["a", "b"]);
+        assert_eq!(crate::json_decode!("[a,b]", 1), ["a"]);
+        assert_eq!(crate::csv_decode!("a;b;c").len(), 3);
+    }
+}
+
+#[overload(unify_return = Graded, dispatch)]
+fn grade(level: u32) -> u64 {
+    u64::from(level) * 10
+}
+
+#[overload]
+fn grade(level: u32, marks: u32) -> Vec<u32> {
+    (0..marks).map(|mark| level * mark).collect()
+}
+
+macros!();
+
+#[test]
+fn test_unify_return() {
+    let results = [grade!(3), grade!(2, 3)];
+    assert!(matches!(results[0], Graded::One(30)));
+    match &results[1] {
+        Graded::Two(marks) => assert_eq!(marks, &[0, 2, 4]),
+        Graded::One(_) => panic!("expected the arity-2 variant"),
+    }
+    assert!(matches!(grade_dispatch(GradeArgs::One(1)), Graded::One(10)));
+}
+
+#[derive(Debug, PartialEq)]
+struct Query {
+    table: String,
+    limit: usize,
+    offset: usize,
+}
+
+impl Query {
+    #[overload(Query, keywords(limit = 10, offset = 0))]
+    fn select(table: &str, limit: usize, offset: usize) -> Self {
+        Self {
+            table: table.to_owned(),
+            limit,
+            offset,
+        }
+    }
+
+    #[overload(Query, keywords)]
+    fn select(table: &str, page: (usize, usize)) -> Self {
+        Self::select_3(table, page.1, page.0 * page.1)
+    }
+}
+
+macros!();
+
+#[test]
+fn test_keywords() {
+    let mut calls = Vec::new();
+    let query = Query_select!(
+        offset = {
+            calls.push("offset");
+            5
+        },
+        table = {
+            calls.push("table");
+            "users"
+        }
+    );
+    assert_eq!(
+        (query.table.as_str(), query.limit, query.offset),
+        ("users", 10, 5)
+    );
+    assert_eq!(calls, ["offset", "table"]);
+    assert_eq!(Query_select!(table = "users", page = (2, 20)).offset, 40);
+    assert_eq!(
+        Query_select!("users", 1, 2),
+        Query_select!(table = "users", limit = 1, offset = 2)
+    );
+}
+
+#[overload]
+fn power(base: u64, exponent: u32) -> u64 {
+    self_overload!(base, exponent, 1)
+}
+
+#[overload]
+fn power(base: u64, exponent: u32, acc: u64) -> u64 {
+    match exponent {
+        0 => acc,
+        _ => self_overload!(base, exponent - 1, acc * base),
+    }
+}
+
+struct Digits(u64);
+
+impl Digits {
+    #[overload(Digits)]
+    fn count(&self) -> usize {
+        self_overload!(self, self.0)
+    }
+
+    #[overload(Digits)]
+    fn count(&self, rest: u64) -> usize {
+        match rest {
+            0..=9 => 1,
+            _ => 1 + self_overload!(self, rest / 10),
+        }
+    }
+}
+
+macros!();
+
+#[test]
+fn test_self_overload() {
+    assert_eq!(power!(2, 10), 1024);
+    assert_eq!(Digits_count!(Digits(12345)), 5);
+}
+
+#[derive(Debug, Default)]
+struct Ledger(Vec<i64>);
+
+impl Ledger {
+    #[overload(Ledger, inherent)]
+    fn record(&mut self, amount: i64) -> usize {
+        self.0.push(amount);
+        self.0.len()
+    }
+
+    #[overload(Ledger, inherent, ext)]
+    fn record(&mut self, amount: i64, times: usize) -> usize {
+        self.0.extend(std::iter::repeat_n(amount, times));
+        self.0.len()
+    }
+}
+
+macros!();
+
+#[test]
+fn test_inherent_method() {
+    let mut ledger = Ledger::default();
+    assert_eq!(ledger.record_any((5,)), 1);
+    assert_eq!(ledger.record_any((-1, 2)), 3);
+    assert_eq!(ledger.record_ov((2, 1)), 4);
+    assert_eq!(ledger.0, [5, -1, -1, 2]);
+}
+
+#[derive(Debug, PartialEq)]
+struct Queue<T, const N: usize>([Option<T>; N]);
+
+#[overload_impl]
+impl<T: Copy, const N: usize> Queue<T, N> {
+    #[overload]
+    fn filled(item: T) -> Self {
+        Self([Some(item); N])
+    }
+
+    #[overload(priority = 1)]
+    fn filled(item: T, count: usize) -> Self {
+        let mut queue = Self([None; N]);
+        queue.0[..count].fill(Some(item));
+        queue
+    }
+
+    #[overload]
+    fn len(&self) -> usize {
+        self.0.iter().flatten().count()
+    }
+
+    #[overload_set("queue_take")]
+    fn take(&mut self, index: usize) -> Option<T> {
+        self.0[index].take()
+    }
+}
+
+macros!();
+
+#[test]
+fn test_overload_impl() {
+    let mut queue: Queue<u8, 3> = Queue_filled!(7, 2);
+    assert_eq!(Queue_len!(queue), 2);
+    assert_eq!(queue_take!(queue, 0), Some(7));
+    assert_eq!(Queue_len!(queue), 1);
+    let filled: Queue<char, 2> = Queue_filled!('a');
+    assert_eq!(filled, Queue([Some('a'); 2]));
+}
+
+#[overload(alias = "temperature")]
+fn celsius(fahrenheit: f64) -> f64 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+struct Thermometer(f64);
+
+impl Thermometer {
+    #[overload(Thermometer, alias = "temperature", alias = "reading")]
+    fn read(&self, offset: f64) -> f64 {
+        self.0 + offset
+    }
+}
+
+macros!();
+
+#[test]
+fn test_alias() {
+    assert_eq!(celsius!(212.0), 100.0);
+    assert_eq!(temperature!(32.0), 0.0);
+    let thermometer = Thermometer(20.0);
+    assert_eq!(temperature!(thermometer, 0.5), 20.5);
+    assert_eq!(
+        reading!(thermometer, -1.0),
+        Thermometer_read!(thermometer, -1.0)
+    );
+}
+
+struct Draft;
+struct Addressed;
+
+struct Mail<State> {
+    to: String,
+    copies: u8,
+    state: std::marker::PhantomData<State>,
+}
+
+#[overload_impl]
+impl Mail<Draft> {
+    #[overload(derive_default)]
+    fn new() -> Self {
+        Mail {
+            to: String::new(),
+            copies: 0,
+            state: std::marker::PhantomData,
+        }
+    }
+
+    #[overload]
+    fn address(self, to: &str) -> Mail<Addressed> {
+        Mail::<Addressed>::new_2(to, self.copies)
+    }
+}
+
+impl Mail<Addressed> {
+    #[overload(Mail<Addressed>)]
+    fn new(to: &str) -> Self {
+        Self::new_2(to, 1)
+    }
+
+    #[overload(Mail<Addressed>)]
+    fn new(to: &str, copies: u8) -> Self {
+        Mail {
+            to: to.to_string(),
+            copies,
+            state: std::marker::PhantomData,
+        }
+    }
+}
+
+macros!();
+
+#[test]
+fn test_type_arguments() {
+    let draft: Mail<Draft> = Mail::default();
+    let mail = Mail_address!(draft, "ana");
+    assert_eq!((mail.to.as_str(), mail.copies), ("ana", 0));
+    let mail = Mail_new!("ben");
+    assert_eq!((mail.to.as_str(), mail.copies), ("ben", 1));
+    let mail = Mail_new!("cai", 3);
+    assert_eq!(mail.copies, 3);
+    assert_eq!(Mail_new!().to, "");
+}
+
+#[overload(keywords(scale = 1))]
+fn midpoint((x1, y1): (i32, i32), (x2, y2): (i32, i32), scale: i32) -> (i32, i32) {
+    ((x1 + x2) / 2 * scale, (y1 + y2) / 2 * scale)
+}
+
+#[overload]
+fn midpoint([x, y]: [i32; 2]) -> (i32, i32) {
+    (x / 2, y / 2)
+}
+
+macros!();
+
+#[test]
+fn test_pattern_parameters() {
+    assert_eq!(midpoint!((0, 0), (4, 2), 1), (2, 1));
+    assert_eq!(midpoint!([6, 4]), (3, 2));
+    assert_eq!(midpoint!(arg_1 = (4, 4), arg_0 = (0, 0)), (2, 2));
+    assert_eq!(midpoint!(arg_0 = (0, 0), arg_1 = (2, 2), scale = 3), (3, 3));
+}
+
+#[overload]
+fn shrink(value: u32) -> u32 {
+    value / 2
+}
+
+#[overload]
+fn shrink(value: u32, times: u32) -> u32 {
+    value >> times
+}
+
+macros!(lints(deny(unused_macros), allow(unused_imports)));
+
+#[test]
+fn test_lints() {
+    assert_eq!(shrink!(8), 4);
+    assert_eq!(shrink!(8, 2), 2);
+}
+
+trait Sensor {
+    fn level(&self) -> u8;
+
+    #[overload(Sensor, trait_def)]
+    fn read(&self) -> u8 {
+        self.level()
+    }
+
+    #[overload(Sensor, trait_def)]
+    fn read(&self, scale: u8) -> u8 {
+        self.level() * scale
+    }
+}
+
+struct Hygrometer(u8);
+
+impl Sensor for Hygrometer {
+    fn level(&self) -> u8 {
+        self.0
+    }
+}
+
+struct Barometer;
+
+impl Sensor for Barometer {
+    fn level(&self) -> u8 {
+        1
+    }
+
+    // Implementations override an overload under its new name.
+    fn read_2(&self, scale: u8) -> u8 {
+        scale
+    }
+}
+
+macros!();
+
+fn read_twice(sensor: &impl Sensor) -> u8 {
+    Sensor_read!(sensor) + Sensor_read!(sensor, 2)
+}
+
+#[test]
+fn test_trait_definitions() {
+    let hygrometer = Hygrometer(3);
+    assert_eq!(Sensor_read!(hygrometer), 3);
+    assert_eq!(Sensor_read!(&hygrometer, 2), 6);
+    assert_eq!(Sensor_read!(Barometer, 5), 5);
+    assert_eq!(read_twice(&Barometer), 3);
+    let sensors: [&dyn Sensor; 2] = [&hygrometer, &Barometer];
+    assert_eq!(sensors.map(|sensor| Sensor_read!(sensor, 4)), [12, 4]);
+}
+
+#[overload(table)]
+fn r#match(pattern: &str) -> bool {
+    pattern.is_empty()
+}
+
+#[overload]
+fn r#match(pattern: &str, text: &str) -> bool {
+    text.contains(pattern)
+}
+
+struct r#Token(u8);
+
+impl r#Token {
+    #[overload(r#Token, ctor)]
+    fn new() -> Self {
+        Self(0)
+    }
+
+    #[overload(r#Token)]
+    fn new(kind: u8) -> Self {
+        Self(kind)
+    }
+
+    #[overload(r#Token)]
+    fn r#type(&self) -> u8 {
+        self.0
+    }
+
+    #[overload(r#Token)]
+    fn r#type(&self, offset: u8) -> u8 {
+        self.0 + offset
+    }
+}
+
+macros!();
+
+#[test]
+fn test_raw_identifiers() {
+    assert!(r#match!(""));
+    assert!(r#match!("b", "abc"));
+    assert!(match_1("") && match_2("b", "abc"));
+    assert_eq!(MATCH_OVERLOADS.len(), 2);
+    assert_eq!(Token_type!(Token_new!(3)), 3);
+    assert_eq!(Token_type!(Token!(), 2), 2);
+    assert_eq!(Token::type_2(&Token(1), 1), 2);
+}
+
+#[overload]
+fn zoom(level: u8) -> u8 {
+    level * 2
+}
+
+#[overload]
+fn aim(angle: u8) -> u8 {
+    angle
+}
+
+#[overload]
+fn zoom(level: u8, step: u8) -> u8 {
+    level * step
+}
+
+macros!(order = registration);
+
+#[test]
+fn test_registration_order() {
+    assert_eq!((zoom!(2), zoom!(2, 3), aim!(4)), (4, 6, 4));
+}
+
+#[overload(place(0))]
+fn jot(log: &mut Vec<u8>, value: u8) {
+    log.push(value);
+}
+
+#[overload(place(0))]
+fn jot(log: &mut Vec<u8>, value: u8, times: usize) {
+    log.extend(std::iter::repeat_n(value, times));
+}
+
+#[overload]
+fn small(value: u8) -> bool {
+    value < 10
+}
+
+#[overload]
+fn small(value: u8, limit: u8) -> bool {
+    value < limit
+}
+
+macros!();
+
+#[test]
+fn test_call_positions() {
+    let mut log = Vec::new();
+    jot!(log, 1);
+    jot!(log, 2, 2);
+    if small!(3) {
+        jot!(log, 3)
+    } else {
+        jot!(log, 0, 3)
+    }
+    let kind = match log.len() {
+        len if small!(len as u8, 4) => "short",
+        len if small!(len as u8) && !small!(len as u8, 2) => "long",
+        _ => "huge",
+    };
+    assert_eq!(kind, "long");
+    let checks = [1, 20].map(|value| small!(value) || small!(value, 30));
+    assert_eq!(checks, [true, true]);
+    let mut push = |value| jot!(log, value, 1);
+    push(4);
+    push(5);
+    assert_eq!(log, [1, 2, 2, 3, 4, 5]);
+    assert!(small!(1) as u8 + small!(1, 2) as u8 == 2);
+}
+
+#[overload(export)]
+pub fn shout(word: &str) -> String {
+    word.to_uppercase()
+}
+
+#[overload]
+pub fn shout(word: &str, times: usize) -> String {
+    word.to_uppercase().repeat(times)
+}
+
+macros!(prelude);
+
+mod downstream {
+    use super::overfn_prelude::*;
+
+    #[test]
+    fn test_prelude() {
+        // Downstream crates import the macro with the glob, here it would be ambiguous with the local one.
+        assert_eq!(super::overfn_prelude::shout!("a", 2), "AA");
+        assert_eq!(shout_1("b"), "B");
+        assert_eq!(shout_2("c", 3), "CCC");
+    }
+}
+
+#[overload]
+fn hash(data: &[u8]) -> u8 {
+    hash_2(data, 0)
+}
+
+#[overload(no_macro)]
+fn hash(data: &[u8], seed: u8) -> u8 {
+    data.iter()
+        .fold(seed, |total, byte| total.wrapping_add(*byte))
+}
+
+#[overload(no_macro)]
+fn parity(data: &[u8]) -> u8 {
+    hash!(data) ^ 0xff
+}
+
+macros!();
+
+#[test]
+fn test_no_macro() {
+    assert_eq!(hash!(@arities), [1]);
+    assert_eq!(hash!([1, 2].as_slice()), 3);
+    assert_eq!(hash_2(&[1, 2], 1), 4);
+    assert_eq!(parity_1(&[0]), 0xff);
+}
+
+mod sizes {
+    use overfn::*;
+
+    #[overload(export, module = sizes)]
+    pub fn grow(size: u32) -> u32 {
+        size + 1
+    }
+
+    #[overload(module = sizes)]
+    pub fn grow(size: u32, by: u32) -> u32 {
+        size + by
+    }
+
+    macros!();
+}
+
+#[test]
+fn test_export_from_module() {
+    assert_eq!(crate::sizes::grow!(1), 2);
+    assert_eq!(crate::sizes::grow!(..[1u32, 2]), 3);
+}
+
+mod north {
+    use overfn::*;
+
+    #[overload]
+    pub fn bearing(degrees: u32) -> u32 {
+        degrees % 360
+    }
+
+    macros!();
+
+    #[test]
+    fn test_sibling_sets() {
+        assert_eq!(bearing!(370), 10);
+    }
+}
+
+mod south {
+    use overfn::*;
+
+    #[overload]
+    pub fn bearing(degrees: u32, turn: u32) -> u32 {
+        (degrees + turn) % 360
+    }
+
+    macros!();
+
+    #[test]
+    fn test_sibling_sets() {
+        assert_eq!(bearing!(350, 20), 10);
+    }
+}
+
+const ADD_ARITIES: &[usize] = &add!(@arities);
+const _: () = assert!(add!(@arity 3) && !add!(@arity 4));
+
+#[test]
+fn test_arity_introspection() {
+    assert_eq!(ADD_ARITIES, [1, 2, 3]);
+    assert_eq!(Test_test!(@arities), [1, 2]);
+    assert_eq!([Test_test!(@arity 2), Test_test!(@arity 0)], [true, false]);
+}
+
+test_suite!(add, cases = { (2) => 12, (2, 2) => 4, (1, 2, 3) => 6 });
+
+test_suite!(Test_test, cases = { (Test_new!(1, 2)) => 3, (Test_new!(1, 2), 3) => 6 });
+
+audit!();
+
+finalize_overloads!();